@@ -0,0 +1,35 @@
+//! every day's solutions behind a single dependency. each day's crate is
+//! re-exported whole, so its parser, part functions and types are all under
+//! the day's module:
+//!
+//! ```
+//! let equations = aoc2024::day07::parse("190: 10 19\n3267: 81 40 27");
+//! assert_eq!(aoc2024::day07::p1(&equations), 3457);
+//! ```
+//!
+//! the types the parts work with are there too:
+//!
+//! ```
+//! use aoc2024::{day06::Direction, day07::Op, day12::Map, day14};
+//!
+//! assert_eq!(Direction::from('^').turn_right(), Direction::from('>'));
+//! assert_eq!(Op::Cat.to_string(), "||");
+//! let map = Map::from("AAB\nAAB");
+//! assert_eq!(map.regions()[&'A'][0].perimeter(), 8);
+//! assert_eq!(day14::BOUNDS.x, 101);
+//! ```
+
+pub use day1 as day01;
+pub use day10;
+pub use day11;
+pub use day12;
+pub use day13;
+pub use day14;
+pub use day2 as day02;
+pub use day3 as day03;
+pub use day4 as day04;
+pub use day5 as day05;
+pub use day6 as day06;
+pub use day7 as day07;
+pub use day8 as day08;
+pub use day9 as day09;
@@ -0,0 +1,58 @@
+use utils::Counter;
+
+pub fn parse(input: &str) -> (Vec<i32>, Vec<i32>) {
+    let mut l1 = Vec::new();
+    let mut l2 = Vec::new();
+    input.lines().for_each(|line| {
+        let mut line = line.split_whitespace();
+        let val = line.next().unwrap();
+        let val = val.trim().parse().expect("couldn't parse val: {val}");
+        l1.push(val);
+        let val = line.next().unwrap();
+        let val = val.trim().parse().expect("couldn't parse val: {val}");
+        l2.push(val);
+    });
+    (l1, l2)
+}
+
+pub fn p1(l1: &[i32], l2: &[i32]) -> i32 {
+    let mut l1 = l1.to_vec();
+    let mut l2 = l2.to_vec();
+    l1.sort();
+    l2.sort();
+    let mut total = 0;
+    for i in 0..l1.len() {
+        let distance = l1[i] - l2[i];
+        total += distance.abs();
+    }
+    total
+}
+
+pub fn p2(l1: &[i32], l2: &[i32]) -> i32 {
+    let counts: Counter<i32> = l2.iter().copied().collect();
+    l1.iter().map(|x| x * counts.get(x) as i32).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r"3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+    #[test]
+    fn test_example() {
+        let (l1, l2) = parse(INPUT);
+        assert_eq!(p1(&l1, &l2), 11);
+        assert_eq!(p2(&l1, &l2), 31);
+    }
+
+    #[test]
+    fn test_trailing_newline() {
+        assert_eq!(parse(&format!("{}\n", INPUT)), parse(INPUT));
+    }
+}
@@ -1,7 +1,10 @@
 pub mod p1 {
     use super::*;
     pub fn solve(input: &str) -> usize {
-        let mut res = input.split(" ").map(|c| c.parse().unwrap()).collect();
+        let mut res = input
+            .split_whitespace()
+            .map(|c| c.parse().unwrap())
+            .collect();
         for _ in 0..25 {
             res = blink(res);
         }
@@ -31,7 +34,7 @@ fn apply(stone: &u128) -> [Option<u128>; 2] {
 
 // given a stone (number) and the result of it log base 10, split it in two by digits
 fn split(stone: u128, log: u32) -> [Option<u128>; 2] {
-    let factor = 10_u128.checked_pow((log + 1) / 2).unwrap();
+    let factor = 10_u128.checked_pow(log.div_ceil(2)).unwrap();
     let left = stone / factor;
     let right = stone - (left * factor);
     [Some(left), Some(right)]
@@ -91,4 +94,10 @@ mod tests {
         }
         assert_eq!(v.len(), 55312);
     }
+
+    #[test]
+    fn test_trailing_newline() {
+        assert_eq!(p1::solve("125 17\n"), 55312);
+        assert_eq!(p2::count("125 17\n", 25), 55312);
+    }
 }
@@ -4,7 +4,7 @@ use day11::{p1, p2};
 
 fn main() {
     let start = Instant::now();
-    let input = std::fs::read_to_string("input.txt").unwrap();
+    let input = utils::input::read("input.txt");
     let res = p1::solve(&input);
     println!("Part 1: {} in {:?}", res, start.elapsed());
     let res = p2::solve(&input);
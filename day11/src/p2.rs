@@ -1,4 +1,6 @@
-use std::{collections::HashMap, time::Instant};
+use std::time::Instant;
+
+use utils::Counter;
 
 pub fn solve(input: &str) -> usize {
     let mut res = Field::from(input);
@@ -10,48 +12,36 @@ pub fn solve(input: &str) -> usize {
     for _ in 0..50 {
         res = blink(res);
     }
-    res.0.values().sum()
+    res.0.total()
 }
 
-struct Field(HashMap<u128, usize>);
-
-impl From<&str> for Field {
-    fn from(value: &str) -> Self {
-        let mut field = HashMap::new();
-        for stone in value.split(" ") {
-            let stone: u128 = stone.parse().unwrap();
-            // increment field at stone
-            let count = *field.entry(stone).or_insert_with(|| 0);
-            field.insert(stone, count + 1);
-        }
-        Field(field)
+/// number of stones after blinking `blinks` times
+pub fn count(input: &str, blinks: usize) -> usize {
+    let mut res = Field::from(input);
+    for _ in 0..blinks {
+        res = blink(res);
     }
+    res.0.total()
 }
 
-impl Field {
-    fn size(&self) -> usize {
-        self.0.values().len()
+struct Field(Counter<u128>);
+
+impl From<&str> for Field {
+    fn from(value: &str) -> Self {
+        Field(
+            value
+                .split_whitespace()
+                .map(|s| s.parse().unwrap())
+                .collect(),
+        )
     }
 }
 
 fn blink(field: Field) -> Field {
-    let mut new = HashMap::with_capacity(2 * field.size());
-    for stone in field.0.keys() {
-        let count = field.0.get(stone).unwrap();
-        match apply(stone) {
-            OutputType::Single(new_stone) => {
-                let count = *new.entry(new_stone).or_insert_with(|| 0) + count;
-                new.insert(new_stone, count);
-            }
-            OutputType::Double(l, r) => {
-                let left_count = *new.entry(l).or_insert_with(|| 0) + count;
-                new.insert(l, left_count);
-                let right_count = *new.entry(r).or_insert_with(|| 0) + count;
-                new.insert(r, right_count);
-            }
-        };
-    }
-    Field(new)
+    Field(field.0.flat_map_keys(|stone| match apply(&stone) {
+        OutputType::Single(new_stone) => vec![new_stone],
+        OutputType::Double(l, r) => vec![l, r],
+    }))
 }
 
 enum OutputType {
@@ -75,7 +65,7 @@ fn apply(stone: &u128) -> OutputType {
 
 // given a stone (number) and the result of it log base 10, split it in two by digits
 fn split(stone: u128, log: u32) -> OutputType {
-    let factor = 10_u128.checked_pow((log + 1) / 2).unwrap();
+    let factor = 10_u128.checked_pow(log.div_ceil(2)).unwrap();
     let left = stone / factor;
     let right = stone - (left * factor);
     OutputType::Double(left, right)
@@ -90,31 +80,25 @@ mod tests {
     #[test]
     fn compare_blinks() {
         let input = "1 2 3 4";
-        let p1 = p1::solve(input);
-        let mut f = Field::from(input);
-        for _ in 0..25 {
-            f = blink(f);
-        }
-        let p2: usize = f.0.values().sum();
-        assert_eq!(p1, p2);
+        assert_eq!(p1::solve(input), count(input, 25));
     }
 
     #[test]
     fn test_blink() {
         let mut f = Field::from("0");
         f = blink(f);
-        assert_eq!(f.0, HashMap::from([(1, 1)]));
+        assert_eq!(f.0, Counter::from([(1, 1)]));
         f = blink(f);
-        assert_eq!(f.0, HashMap::from([(2024, 1)]));
+        assert_eq!(f.0, Counter::from([(2024, 1)]));
         f = blink(f);
-        assert_eq!(f.0, HashMap::from([(20, 1), (24, 1)]));
+        assert_eq!(f.0, Counter::from([(20, 1), (24, 1)]));
         f = Field::from("0 0");
         f = blink(f);
-        assert_eq!(f.0, HashMap::from([(1, 2)]));
+        assert_eq!(f.0, Counter::from([(1, 2)]));
         f = blink(f);
-        assert_eq!(f.0, HashMap::from([(2024, 2)]));
+        assert_eq!(f.0, Counter::from([(2024, 2)]));
         f = blink(f);
-        assert_eq!(f.0, HashMap::from([(20, 2), (24, 2)]));
+        assert_eq!(f.0, Counter::from([(20, 2), (24, 2)]));
         for _ in 3..7 {
             f = blink(f)
         }
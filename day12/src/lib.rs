@@ -1,14 +1,24 @@
 use std::collections::HashMap;
 
+use utils::visited::Visited;
+
 pub mod p1;
 pub mod p2;
 
 pub fn read_input(filename: &str) -> String {
     println!("Reading input from {}", filename);
-    std::fs::read_to_string(filename).expect("Failed to read input file")
+    utils::input::read(filename)
 }
 
-struct Map {
+/// serialized as its grid, one string with a line per row. any grid is a
+/// valid garden, so there's nothing to check when deserializing it
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "String", from = "String")
+)]
+pub struct Map {
     data: Vec<Vec<char>>,
 }
 
@@ -20,8 +30,24 @@ impl From<&str> for Map {
     }
 }
 
+impl From<String> for Map {
+    fn from(input: String) -> Self {
+        Map::from(input.as_str())
+    }
+}
+
+impl From<Map> for String {
+    fn from(map: Map) -> String {
+        map.data
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
 impl Map {
-    fn regions(&self) -> HashMap<char, Vec<Region>> {
+    pub fn regions(&self) -> HashMap<char, Vec<Region>> {
         let mut locations_by_char = HashMap::new();
         for (y, row) in self.data.iter().enumerate() {
             for (x, &cell) in row.iter().enumerate() {
@@ -42,57 +68,80 @@ impl Map {
             })
             .collect()
     }
+
+    /// the same as `regions`, but in a stable order so they can be listed
+    pub fn sorted_regions(&self) -> Vec<(char, Region)> {
+        let mut regions: Vec<(char, Vec<Region>)> =
+            self.regions().into_iter().collect();
+        regions.sort_by_key(|(c, _)| *c);
+        regions
+            .into_iter()
+            .flat_map(|(c, rs)| rs.into_iter().map(move |r| (c, r)))
+            .collect()
+    }
+}
+
+/// lists each region's price as its area times `measure`, which is all
+/// that differs between the parts
+fn explain(input: &str, measure: fn(&Region) -> usize) -> Vec<String> {
+    Map::from(input)
+        .sorted_regions()
+        .iter()
+        .map(|(c, r)| {
+            format!(
+                "A region of {} plants with price {} * {} = {}.",
+                c,
+                r.area(),
+                measure(r),
+                r.area() * measure(r)
+            )
+        })
+        .collect()
 }
 
+/// splits plants of one kind into regions, flood filling from each plant
+/// that isn't in a region yet
 fn regions(locations: &Vec<(usize, usize)>) -> Vec<Region> {
-    let mut regions: Vec<Region> = vec![];
-    let mut found_region = false;
-    for location in locations {
-        for region in &mut regions {
-            if region
-                .locations
-                .iter()
-                .any(|l| is_adjacent(&&(*l, *location)))
-            {
-                region.locations.push(*location);
-                found_region = true;
-                break;
-            }
-        }
-        if !found_region {
-            regions.push(Region {
-                locations: vec![*location],
-            });
-        }
-        found_region = false;
+    // state 0 marks where the plants are, state 1 those already in a region
+    let width = locations.iter().map(|l| l.0 + 1).max().unwrap_or(0);
+    let height = locations.iter().map(|l| l.1 + 1).max().unwrap_or(0);
+    let mut seen = Visited::new(width, height, 2);
+    for &(x, y) in locations {
+        seen.insert(x, y, 0);
     }
 
-    while regions
-        .iter()
-        .any(|r| regions.iter().any(|s| r.should_merge(s)))
-    {
-        let mut merged_regions = vec![];
-        for region in &regions {
-            let mut merged = false;
-            for merged_region in &mut merged_regions {
-                if region.should_merge(merged_region) {
-                    merged_region.merge(region);
-                    merged = true;
-                    break;
+    let mut regions = vec![];
+    for &(x, y) in locations {
+        if !seen.insert(x, y, 1) {
+            continue;
+        }
+        let mut region = vec![(x, y)];
+        let mut i = 0;
+        while let Some(&(x, y)) = region.get(i) {
+            i += 1;
+            let neighbours = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (x, y) in neighbours {
+                if x < width
+                    && y < height
+                    && seen.contains(x, y, 0)
+                    && seen.insert(x, y, 1)
+                {
+                    region.push((x, y));
                 }
             }
-            if !merged {
-                merged_regions.push(region.clone());
-            }
         }
-        regions = merged_regions;
+        regions.push(Region { locations: region });
     }
-
     regions
 }
 
 #[derive(Clone)]
-struct Region {
+pub struct Region {
     locations: Vec<(usize, usize)>,
 }
 
@@ -103,7 +152,11 @@ impl PartialEq for Region {
 }
 
 impl Region {
-    fn area(&self) -> usize {
+    pub fn locations(&self) -> &[(usize, usize)] {
+        &self.locations
+    }
+
+    pub fn area(&self) -> usize {
         self.locations.len()
     }
 
@@ -111,24 +164,13 @@ impl Region {
         perm(&self.locations).iter().filter(is_adjacent).count() / 2
     }
 
-    fn perimeter(&self) -> usize {
+    pub fn perimeter(&self) -> usize {
         self.area() * 4 - 2 * self.num_adjacent_locations()
     }
 
-    fn sides(&self) -> usize {
+    pub fn sides(&self) -> usize {
         0
     }
-
-    fn should_merge(&self, other: &Region) -> bool {
-        self != other
-            && self.locations.iter().any(|l| {
-                other.locations.iter().any(|m| is_adjacent(&&(*l, *m)))
-            })
-    }
-
-    fn merge(&mut self, other: &Region) {
-        self.locations.extend(other.locations.iter().copied());
-    }
 }
 
 fn perm<T: Copy>(v: &[T]) -> Vec<(T, T)> {
@@ -150,7 +192,7 @@ fn is_adjacent(pair: &&((usize, usize), (usize, usize))) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use crate::{p1, read_input, regions, Map};
+    use crate::{p1, p2, read_input, regions, Map};
 
     #[test]
     fn test_region() {
@@ -176,6 +218,16 @@ mod tests {
         assert_eq!(p1::price(&region), 32);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let map = Map::from("AAB\nACB");
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#""AAB\nACB""#);
+        let back: Map = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.data, map.data);
+    }
+
     #[test]
     fn test_internal() {
         let input = r"AAA
@@ -208,6 +260,21 @@ AAA";
         assert_eq!(regions, 3);
     }
 
+    #[test]
+    fn test_explain() {
+        let lines = p1::explain("AAAA\nBBCD\nBBCC\nEEEC");
+        assert_eq!(
+            lines,
+            vec![
+                "A region of A plants with price 4 * 10 = 40.",
+                "A region of B plants with price 4 * 8 = 32.",
+                "A region of C plants with price 4 * 10 = 40.",
+                "A region of D plants with price 1 * 4 = 4.",
+                "A region of E plants with price 3 * 8 = 24.",
+            ]
+        );
+    }
+
     #[test]
     fn test_input() {
         let input = read_input("test_input.txt");
@@ -244,4 +311,11 @@ A region of S plants with price 3 * 8 = 24.";
         // assert_eq!(regions.get(&'S').unwrap().price(), 25);
         assert_eq!(total_price, 1930);
     }
+
+    #[test]
+    fn test_trailing_newline() {
+        let input = format!("{}\n", read_input("test_input.txt"));
+        assert_eq!(p1::solve(&input), 1930);
+        assert_eq!(p2::solve(&input), p2::solve(input.trim_end()));
+    }
 }
@@ -9,6 +9,11 @@ pub fn solve(input: &str) -> usize {
         .sum()
 }
 
+/// lists the price of every region, the same way the puzzle does
+pub fn explain(input: &str) -> Vec<String> {
+    crate::explain(input, Region::sides)
+}
+
 fn price(r: &Region) -> usize {
     r.area() * r.sides()
 }
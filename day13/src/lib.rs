@@ -6,8 +6,43 @@ pub fn p2(input: &[Machine]) -> usize {
     input.iter().filter_map(solve).map(cost).sum::<i64>() as usize
 }
 
+/// how many presses of each button it takes to win each machine's prize
+pub fn explain_p1(input: &[Machine]) -> Vec<String> {
+    explain(input, solve_p1)
+}
+
+pub fn explain_p2(input: &[Machine]) -> Vec<String> {
+    explain(input, solve)
+}
+
+fn explain(
+    input: &[Machine],
+    solver: fn(&Machine) -> Option<Solution>,
+) -> Vec<String> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, m)| match solver(m) {
+            Some(s) => {
+                let (a, b) = (s.a, s.b);
+                format!(
+                    "machine {}: {} A presses, {} B presses, {} tokens",
+                    i + 1,
+                    a,
+                    b,
+                    cost(s)
+                )
+            }
+            None => format!("machine {}: can't win", i + 1),
+        })
+        .collect()
+}
+
 pub fn load_input(filename: &str) -> Vec<Machine> {
-    let input = std::fs::read_to_string(filename).unwrap();
+    parse(&utils::input::read(filename))
+}
+
+pub fn parse(input: &str) -> Vec<Machine> {
     let mut res = Vec::new();
     let mut a = None;
     let mut b = None;
@@ -39,6 +74,7 @@ pub fn modify_input(input: &mut [Machine]) {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Location {
     x: i64,
     y: i64,
@@ -46,12 +82,47 @@ struct Location {
 
 // prize is at a location (p), and the buttons a and b change the current location by dx and dy (stored as location)
 #[derive(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "UncheckedMachine")
+)]
 pub struct Machine {
     a: Location,
     b: Location,
     p: Location,
 }
 
+// a machine as it comes in, before checking its buttons move the claw
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedMachine {
+    a: Location,
+    b: Location,
+    p: Location,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedMachine> for Machine {
+    type Error = String;
+
+    fn try_from(m: UncheckedMachine) -> Result<Machine, String> {
+        for (name, button) in [("a", &m.a), ("b", &m.b)] {
+            if button.x <= 0 || button.y <= 0 {
+                return Err(format!(
+                    "button {name} moves by {:?}, not forward in both x and y",
+                    (button.x, button.y)
+                ));
+            }
+        }
+        Ok(Machine {
+            a: m.a,
+            b: m.b,
+            p: m.p,
+        })
+    }
+}
+
 impl From<&str> for Location {
     fn from(value: &str) -> Self {
         let x_loc = value.find('X').unwrap();
@@ -119,6 +190,31 @@ fn solve_p1(m: &Machine) -> Option<Solution> {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let machines = parse(
+            "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400",
+        );
+        let json = serde_json::to_string(&machines).unwrap();
+        assert_eq!(
+            json,
+            concat!(
+                r#"[{"a":{"x":94,"y":34},"b":{"x":22,"y":67},"#,
+                r#""p":{"x":8400,"y":5400}}]"#
+            )
+        );
+        let back: Vec<Machine> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, machines);
+
+        let json = json.replace(r#""x":22"#, r#""x":0"#);
+        let e = serde_json::from_str::<Vec<Machine>>(&json).unwrap_err();
+        assert!(
+            e.to_string().starts_with("button b moves by (0, 67)"),
+            "{e}"
+        );
+    }
+
     #[test]
     fn test_load_input() {
         let input = load_input("test_input.txt");
@@ -165,9 +261,30 @@ mod tests {
         assert_eq!(cost, 280);
     }
 
+    #[test]
+    fn test_explain() {
+        let input = load_input("test_input.txt");
+        assert_eq!(
+            explain_p1(&input),
+            vec![
+                "machine 1: 80 A presses, 40 B presses, 280 tokens",
+                "machine 2: can't win",
+                "machine 3: 38 A presses, 86 B presses, 200 tokens",
+                "machine 4: can't win",
+            ]
+        );
+    }
+
     #[test]
     fn test_full_test_input() {
         let input = load_input("test_input.txt");
         assert_eq!(p1(&input), 480);
     }
+
+    #[test]
+    fn test_trailing_newline() {
+        let input =
+            "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400";
+        assert_eq!(parse(&format!("{}\n", input)), parse(input));
+    }
 }
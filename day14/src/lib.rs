@@ -1,22 +1,75 @@
-use std::collections::HashMap;
-
 pub use robot::{Location, Robot};
+use utils::Counter;
 
 pub mod ee;
 mod robot;
 
+/// the grid the robots walk around on. the examples use a smaller one
+pub const BOUNDS: Location = Location { x: 101, y: 103 };
+
 pub mod p1 {
-    use crate::{robot::Location, safety_factor};
+    use crate::{robot::Location, safety_factor, Robot};
 
     pub fn solve(filename: &str, bounds: Location) -> usize {
-        let mut robots = super::load_input(filename);
+        run(super::load_input(filename), bounds)
+    }
+
+    pub fn run(mut robots: Vec<Robot>, bounds: Location) -> usize {
         robots.iter_mut().for_each(|r| r.step(100, bounds));
         safety_factor(&robots, bounds)
     }
 }
 
+pub mod p2 {
+    use utils::{
+        cycle,
+        progress::{Cancelled, Ignore, Progress},
+    };
+
+    use crate::{robot::Location, safety_factor, Robot};
+
+    /// the robots wrap around the edges, so they eventually end up back
+    /// where they started. returns how many steps that takes
+    pub fn period(robots: &[Robot], bounds: Location) -> usize {
+        let step = |mut robots: Vec<Robot>| {
+            robots.iter_mut().for_each(|r| r.step(1, bounds));
+            robots
+        };
+        cycle::brent(robots.to_vec(), step).length
+    }
+
+    /// how many steps until the robots first look like a christmas tree.
+    /// the tree bunches most of the robots up in one place, while the rest
+    /// of the time they're spread all over, so it's the step out of the
+    /// period with the lowest safety factor
+    pub fn first_tree(robots: Vec<Robot>, bounds: Location) -> usize {
+        first_tree_with_progress(robots, bounds, &mut Ignore)
+            .expect("ignoring progress never cancels")
+    }
+
+    /// `first_tree`, reporting each step out of the period
+    pub fn first_tree_with_progress(
+        mut robots: Vec<Robot>,
+        bounds: Location,
+        progress: &mut dyn Progress,
+    ) -> Result<usize, Cancelled> {
+        let period = period(&robots, bounds);
+        // the lowest factor, and the earliest step that has it
+        let mut best = (safety_factor(&robots, bounds), 0);
+        for i in 1..period {
+            robots.iter_mut().for_each(|r| r.step(1, bounds));
+            best = best.min((safety_factor(&robots, bounds), i));
+            progress.update(i, period)?;
+        }
+        Ok(best.1)
+    }
+}
+
 pub fn load_input(filename: &str) -> Vec<Robot> {
-    let input = std::fs::read_to_string(filename).unwrap();
+    parse(&utils::input::read(filename))
+}
+
+pub fn parse(input: &str) -> Vec<Robot> {
     input.lines().map(|l| l.trim().into()).collect()
 }
 
@@ -24,14 +77,9 @@ fn safety_factor(robots: &[Robot], bounds: Location) -> usize {
     robots
         .iter()
         .map(Robot::loc)
-        .map(|l| (quadrant(bounds, l), l))
-        .filter(|(q, _)| q.is_some())
-        .map(|(q, l)| (q.unwrap(), l))
-        .fold(HashMap::new(), |mut map: HashMap<usize, usize>, (q, _)| {
-            *map.entry(q).or_insert(0) += 1;
-            map
-        })
-        .values()
+        .filter_map(|l| quadrant(bounds, l))
+        .collect::<Counter<usize>>()
+        .counts()
         .product()
 }
 
@@ -62,8 +110,20 @@ fn quadrant(bounds: Location, loc: Location) -> Option<usize> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let robots = parse("p=0,4 v=3,-3");
+        let json = serde_json::to_string(&robots).unwrap();
+        assert_eq!(json, r#"[{"p":{"x":0,"y":4},"v":{"x":3,"y":-3}}]"#);
+        let back: Vec<Robot> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, robots);
+    }
+
     #[test]
     fn test_solve() {
         let input = "test_input.txt";
@@ -71,6 +131,62 @@ mod tests {
         assert_eq!(p1::solve(input, bounds), 12);
     }
 
+    #[test]
+    fn test_period() {
+        let robots = load_input("test_input.txt");
+        let bounds = Location { x: 11, y: 7 };
+        assert_eq!(p2::period(&robots, bounds), 77);
+    }
+
+    #[test]
+    fn test_first_tree_progress() {
+        let robots = load_input("test_input.txt");
+        let bounds = Location { x: 11, y: 7 };
+        let mut last = (0, 0);
+        let res =
+            p2::first_tree_with_progress(robots, bounds, &mut |done, total| {
+                last = (done, total);
+                true
+            });
+        assert_eq!(
+            res,
+            Ok(p2::first_tree(load_input("test_input.txt"), bounds))
+        );
+        assert_eq!(last.1, 77);
+    }
+
+    #[test]
+    fn test_first_tree() {
+        // 500 robots on the full grid, like a real input: 441 of them make
+        // a solid triangle after 4321 steps, the rest are scattered
+        let steps = 4321;
+        let mut seed: u64 = 2024;
+        let mut random = |n: i64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            ((seed >> 33) % n as u64) as i64
+        };
+        let mut tree: Vec<(i64, i64)> = (0..21)
+            .flat_map(|r| (25 - r..=25 + r).map(move |x| (x, 10 + r)))
+            .collect();
+        while tree.len() < 500 {
+            tree.push((random(101), random(103)));
+        }
+        let input = tree
+            .iter()
+            .map(|(x, y)| {
+                let (vx, vy) = (random(201) - 100, random(201) - 100);
+                let px = (x - vx * steps).rem_euclid(101);
+                let py = (y - vy * steps).rem_euclid(103);
+                format!("p={},{} v={},{}", px, py, vx, vy)
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let robots = parse(&input);
+        assert_eq!(robots.len(), 500);
+        assert_eq!(p2::first_tree(robots, BOUNDS), steps as usize);
+    }
+
     #[test]
     fn test_quadrant() {
         let bounds = Location { x: 11, y: 7 };
@@ -207,4 +323,9 @@ mod tests {
         assert_eq!(r.loc(), Location { x: 0, y: 0 });
         assert_eq!(quadrant(bounds, r.loc()), Some(0));
     }
+
+    #[test]
+    fn test_trailing_newline() {
+        assert_eq!(parse("p=0,4 v=3,-3\n"), parse("p=0,4 v=3,-3"));
+    }
 }
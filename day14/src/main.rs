@@ -1,13 +1,15 @@
-use day14::{ee, load_input, p1, Location, Robot};
+use day14::{ee, load_input, p1, p2, Robot, BOUNDS};
 
 fn main() {
     let input = "input.txt";
-    let bounds = Location { x: 101, y: 103 };
+    let bounds = BOUNDS;
     println!("Part 1: {}", p1::solve(input, bounds));
 
     let mut robots = load_input(input);
-    let mut i = 0;
-    loop {
+    // after this many steps the robots are back where they started,
+    // so there's no point searching any further
+    let period = p2::period(&robots, bounds);
+    for i in 0..period {
         robots.iter_mut().for_each(|r| r.step(1, bounds));
         let locs = robots.iter().map(Robot::loc).collect();
         let map = ee::Map { bounds, locs };
@@ -16,6 +18,5 @@ fn main() {
             println!("{}", map);
             // sleep(Duration::from_millis(100));
         }
-        i += 1;
     }
 }
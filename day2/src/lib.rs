@@ -0,0 +1,143 @@
+pub fn parse(input: &str) -> Vec<Vec<i32>> {
+    input
+        .lines()
+        .map(|line| {
+            let level = line.split_whitespace();
+            let levels = level
+                .into_iter()
+                .map(|l| l.trim().parse::<i32>().expect("error parsing level"))
+                .collect::<Vec<i32>>();
+            levels
+        })
+        .collect::<Vec<Vec<i32>>>()
+}
+
+pub fn p1(reports: &[Vec<i32>]) -> usize {
+    reports.iter().filter(|r| is_safe(r)).count()
+}
+
+pub fn p2(reports: &[Vec<i32>]) -> usize {
+    reports.iter().filter(|r| is_safe_dampened(r)).count()
+}
+
+/// lists the unsafe reports
+pub fn explain_p1(reports: &[Vec<i32>]) -> Vec<String> {
+    reports
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| !is_safe(r))
+        .map(|(i, r)| format!("report {} {:?} is unsafe", i + 1, r))
+        .collect()
+}
+
+/// lists the unsafe reports, and which level the dampener removed to fix them
+pub fn explain_p2(reports: &[Vec<i32>]) -> Vec<String> {
+    reports
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| !is_safe(r))
+        .map(|(i, r)| match dampened_level(r) {
+            Some(j) => format!(
+                "report {} {:?} is safe after removing level {} ({})",
+                i + 1,
+                r,
+                j + 1,
+                r[j]
+            ),
+            None => format!(
+                "report {} {:?} is unsafe even with the dampener",
+                i + 1,
+                r
+            ),
+        })
+        .collect()
+}
+
+fn is_safe_dampened(report: &[i32]) -> bool {
+    dampened_level(report).is_some()
+}
+
+/// the index of the first level that can be removed to make the report safe
+fn dampened_level(report: &[i32]) -> Option<usize> {
+    (0..report.len()).find(|i| {
+        let mut new = report.to_owned();
+        new.remove(*i);
+        is_safe(&new)
+    })
+}
+
+fn is_safe(report: &[i32]) -> bool {
+    let mut increasing = Option::None;
+    for i in 0..report.len() - 1 {
+        let a = report[i];
+        let b = report[i + 1];
+        let diff = b - a;
+        if diff == 0 {
+            return false;
+        }
+        match increasing {
+            None => increasing = Some(diff > 0),
+            Some(true) => {
+                if diff < 0 {
+                    return false;
+                }
+            }
+            Some(false) => {
+                if diff > 0 {
+                    return false;
+                }
+            }
+        }
+        if diff.abs() > 3 {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r"7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+    #[test]
+    fn test_example() {
+        let reports = parse(INPUT);
+        assert_eq!(p1(&reports), 2);
+        assert_eq!(p2(&reports), 4);
+    }
+
+    #[test]
+    fn test_explain() {
+        let reports = parse(INPUT);
+        assert_eq!(
+            explain_p1(&reports),
+            vec![
+                "report 2 [1, 2, 7, 8, 9] is unsafe",
+                "report 3 [9, 7, 6, 2, 1] is unsafe",
+                "report 4 [1, 3, 2, 4, 5] is unsafe",
+                "report 5 [8, 6, 4, 4, 1] is unsafe",
+            ]
+        );
+        assert_eq!(
+            explain_p2(&reports),
+            vec![
+                "report 2 [1, 2, 7, 8, 9] is unsafe even with the dampener",
+                "report 3 [9, 7, 6, 2, 1] is unsafe even with the dampener",
+                "report 4 [1, 3, 2, 4, 5] is safe after removing level 2 (3)",
+                "report 5 [8, 6, 4, 4, 1] is safe after removing level 3 (4)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trailing_newline() {
+        assert_eq!(parse(&format!("{}\n", INPUT)), parse(INPUT));
+    }
+}
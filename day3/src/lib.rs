@@ -0,0 +1,43 @@
+use regex::Regex;
+
+pub fn p1(input: &str) -> i32 {
+    get_total(&mul_regex(), input)
+}
+
+pub fn p2(input: &str) -> i32 {
+    let regex = mul_regex();
+    let sections = input
+        .split("do()")
+        .map(|s| s.split("don't()").collect::<Vec<&str>>()[0].to_string())
+        .collect::<Vec<String>>();
+
+    sections
+        .into_iter()
+        .map(|s| get_total(&regex, s.as_str()))
+        .sum()
+}
+
+fn mul_regex() -> Regex {
+    Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap()
+}
+
+fn get_total(rx: &Regex, input: &str) -> i32 {
+    rx.captures_iter(input).fold(0, |acc, cap| {
+        let a: i32 = cap[1].parse().unwrap();
+        let b: i32 = cap[2].parse().unwrap();
+        acc + a * b
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trailing_newline() {
+        let input =
+            "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))\n";
+        assert_eq!(p1(input), 161);
+        assert_eq!(p2(input), 48);
+    }
+}
@@ -14,10 +14,9 @@ pub fn p1(rows: Vec<String>) -> usize {
     matches
 }
 
-pub fn p2(rows: Vec<String>) {
+pub fn p2(rows: Vec<String>) -> usize {
     let chunks = get_chunks(rows);
-    let matches: usize = chunks.iter().filter(|chunk| has_xmas(chunk)).count();
-    println!("Part 2: {}", matches);
+    chunks.iter().filter(|chunk| has_xmas(chunk)).count()
 }
 
 fn has_xmas(chunk: &Chunk) -> bool {
@@ -100,9 +99,7 @@ fn rotate_diag(rows: Vec<String>) -> Vec<String> {
         for _ in 0..r {
             rotated[r].push('.');
         }
-        for c in 0..rows[r].len() {
-            rotated[r].push(rows[r][c]);
-        }
+        rotated[r].extend_from_slice(&rows[r]);
         for _ in r..side_length - rows[r].len() {
             rotated[r].push('.');
         }
@@ -112,11 +109,11 @@ fn rotate_diag(rows: Vec<String>) -> Vec<String> {
 }
 
 pub fn load(filename: &str) -> Vec<String> {
-    std::fs::read_to_string(filename)
-        .unwrap()
-        .lines()
-        .map(String::from)
-        .collect()
+    parse(&utils::input::read(filename))
+}
+
+pub fn parse(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
 }
 
 #[cfg(test)]
@@ -184,4 +181,9 @@ mod tests {
         }
         println!();
     }
+
+    #[test]
+    fn test_trailing_newline() {
+        assert_eq!(parse("XMAS\nSAMX\n"), ["XMAS", "SAMX"]);
+    }
 }
@@ -0,0 +1,319 @@
+use std::{collections::HashMap, fmt::Display};
+
+use log::debug;
+
+/// a hashmap where the value is a list of page numbers that must come before the key
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rules(HashMap<String, Vec<String>>);
+
+impl Rules {
+    // these aren't necessary, but they remove the `.0` from code below
+    fn get(&self, key: &str) -> Option<&Vec<String>> {
+        self.0.get(key)
+    }
+
+    fn add(&mut self, val: &str, prerequisite: &str) {
+        match self.0.get_mut(val) {
+            Some(prereqs) => prereqs.push(prerequisite.to_string()),
+            None => {
+                self.0
+                    .insert(val.to_string(), vec![prerequisite.to_string()]);
+            }
+        }
+    }
+}
+
+/// a list of page numbers
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Vec<String>")
+)]
+pub struct Update(Vec<String>);
+
+/// checked as it's deserialized, since every update needs a middle page
+impl TryFrom<Vec<String>> for Update {
+    type Error = String;
+
+    fn try_from(pages: Vec<String>) -> Result<Update, String> {
+        if pages.is_empty() {
+            return Err("an update needs at least one page".to_string());
+        }
+        Ok(Update(pages))
+    }
+}
+
+impl Update {
+    fn new() -> Update {
+        Update(Vec::new())
+    }
+
+    fn from(line: &str) -> Update {
+        let pages = line.split(",").map(|s| s.to_string()).collect();
+        Update(pages)
+    }
+
+    fn contains(&self, page: &String) -> bool {
+        self.0.contains(page)
+    }
+
+    fn append(&mut self, pages: &mut Vec<String>) {
+        self.0.append(pages);
+    }
+
+    fn iter(&self) -> std::slice::Iter<'_, String> {
+        self.0.iter()
+    }
+
+    fn middle(&self) -> i32 {
+        self.0[self.0.len() / 2].clone().parse().unwrap()
+    }
+
+    fn at(&self, i: usize) -> &String {
+        &self.0[i]
+    }
+
+    fn get_pages_before(&self, i: usize) -> &[String] {
+        &self.0[0..i]
+    }
+}
+
+impl Display for Update {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join(","))
+    }
+}
+
+pub fn p1(rules: &Rules, updates: &[Update]) -> i32 {
+    updates
+        .iter()
+        .filter(|u| follows_rules(rules, u))
+        .map(|u| u.middle())
+        .sum()
+}
+
+pub fn p2(rules: &Rules, updates: &[Update]) -> i32 {
+    updates
+        .iter()
+        .filter(|u| !follows_rules(rules, u))
+        .map(|u| fix_update(rules, u))
+        .map(|u| u.middle())
+        .sum()
+}
+
+/// lists the updates that are out of order, and the rule each one breaks
+pub fn explain_p1(rules: &Rules, updates: &[Update]) -> Vec<String> {
+    updates
+        .iter()
+        .filter_map(|u| {
+            broken_rule(rules, u).map(|(before, after)| {
+                format!("{} breaks rule {}|{}", u, before, after)
+            })
+        })
+        .collect()
+}
+
+/// lists how each out of order update was fixed
+pub fn explain_p2(rules: &Rules, updates: &[Update]) -> Vec<String> {
+    updates
+        .iter()
+        .filter(|u| !follows_rules(rules, u))
+        .map(|u| {
+            let fixed = fix_update(rules, u);
+            format!("{} -> {} (middle {})", u, fixed, fixed.middle())
+        })
+        .collect()
+}
+
+pub fn parse(input: &str) -> (Rules, Vec<Update>) {
+    parse_input(input.lines().map(String::from).collect())
+}
+
+fn parse_input(lines: Vec<String>) -> (Rules, Vec<Update>) {
+    let mut in_rules_section = true;
+    let mut rules = Rules(HashMap::new());
+    let mut updates = Vec::new();
+    for line in lines {
+        if line.is_empty() {
+            in_rules_section = false;
+            continue;
+        }
+
+        if in_rules_section {
+            let (a, b) = line.split_once("|").unwrap();
+            rules.add(b, a);
+        } else {
+            updates.push(Update::from(&line));
+        }
+    }
+
+    (rules, updates)
+}
+
+fn follows_rules(rules: &Rules, update: &Update) -> bool {
+    broken_rule(rules, update).is_none()
+}
+
+/// the first rule the update breaks, as (prerequisite, page)
+fn broken_rule<'a>(
+    rules: &'a Rules,
+    update: &'a Update,
+) -> Option<(&'a String, &'a String)> {
+    for (i, page) in update.iter().enumerate() {
+        if let Some(prereqs) = rules.get(page) {
+            let follows = update.get_pages_before(i);
+            let should_follow: Vec<&String> =
+                prereqs.iter().filter(|s| update.contains(s)).collect();
+            if let Some(missing) =
+                should_follow.iter().find(|s| !follows.contains(s))
+            {
+                debug!(
+                    "{:?} doesn't follow at {} because {} should follow {:?}",
+                    update,
+                    i,
+                    update.at(i),
+                    should_follow,
+                );
+                return Some((missing, page));
+            }
+        }
+    }
+    None
+}
+
+/// reorders the update so that it follows the rules
+fn fix_update(rules: &Rules, update: &Update) -> Update {
+    let mut new_update = Update::new();
+
+    let not_yet_added = |u: &&String, dest: &Update| !dest.contains(u);
+    let remaining_requirements = |n, dest: &Update| {
+        rules
+            .get(n)
+            .into_iter()
+            .flatten()
+            .filter(|f| update.contains(f) && !dest.contains(f))
+            .collect::<Vec<&String>>()
+    };
+
+    loop {
+        let requirements: Vec<(&String, Vec<&String>)> = update
+            .iter()
+            .filter(|u| not_yet_added(u, &new_update)) // numbers we haven't added yet
+            .map(|n| (n, remaining_requirements(n, &new_update)))
+            .collect();
+
+        if requirements.is_empty() {
+            // no more requirements, done!
+            return new_update;
+        }
+
+        // get any numbers with zero remaining requirements
+        let mut next: Vec<String> = requirements
+            .iter()
+            .filter(|(_, v)| v.is_empty())
+            .map(|(k, _)| k.to_string())
+            .collect();
+
+        if next.is_empty() {
+            // if we still have requirements but nothing can be added, we panic
+            panic!(
+                "no solution for {:?} with rules: {:?}",
+                update, requirements
+            );
+        }
+
+        // add any numbers without requirements
+        new_update.append(&mut next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r"47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+    #[test]
+    fn test_example() {
+        let (rules, updates) = parse(INPUT);
+        assert_eq!(p1(&rules, &updates), 143);
+        assert_eq!(p2(&rules, &updates), 123);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let (rules, updates) = parse(INPUT);
+        let json = serde_json::to_string(&updates[..1]).unwrap();
+        assert_eq!(json, r#"[["75","47","61","53","29"]]"#);
+
+        let rules: Rules =
+            serde_json::from_str(&serde_json::to_string(&rules).unwrap())
+                .unwrap();
+        let updates: Vec<Update> =
+            serde_json::from_str(&serde_json::to_string(&updates).unwrap())
+                .unwrap();
+        assert_eq!(p1(&rules, &updates), 143);
+        assert_eq!(p2(&rules, &updates), 123);
+
+        let e = serde_json::from_str::<Vec<Update>>("[[]]").unwrap_err();
+        assert!(e.to_string().starts_with("an update needs"), "{}", e);
+    }
+
+    #[test]
+    fn test_explain() {
+        let (rules, updates) = parse(INPUT);
+        assert_eq!(
+            explain_p1(&rules, &updates),
+            vec![
+                "75,97,47,61,53 breaks rule 97|75",
+                "61,13,29 breaks rule 29|13",
+                "97,13,75,29,47 breaks rule 29|13",
+            ]
+        );
+        assert_eq!(
+            explain_p2(&rules, &updates),
+            vec![
+                "75,97,47,61,53 -> 97,75,47,61,53 (middle 47)",
+                "61,13,29 -> 61,29,13 (middle 29)",
+                "97,13,75,29,47 -> 97,75,47,29,13 (middle 47)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trailing_newline() {
+        let (rules, updates) = parse(&format!("{}\n", INPUT));
+        assert_eq!(p1(&rules, &updates), 143);
+        assert_eq!(p2(&rules, &updates), 123);
+    }
+}
@@ -31,6 +31,22 @@ impl From<Direction> for char {
 }
 
 impl Direction {
+    pub const COUNT: usize = 4;
+
+    /// a number for each direction, from 0 up to `COUNT`
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
     pub fn dx(&self) -> i32 {
         match self {
             Direction::Up => 0,
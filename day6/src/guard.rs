@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use utils::visited::Visited;
 
 use crate::{
     direction::Direction, error::GoError, location::Location, map::Map,
@@ -13,16 +13,16 @@ pub struct Guard<'a> {
     map: &'a Map,
 }
 
-struct LocationHistory(HashSet<Vector>);
+struct LocationHistory(Visited);
 impl LocationHistory {
-    fn start(loc: Vector) -> LocationHistory {
-        let mut v = HashSet::new();
-        v.insert(loc);
+    fn start(loc: Vector, map: &Map) -> LocationHistory {
+        let mut v = Visited::new(map.width(), map.height(), Direction::COUNT);
+        v.insert(loc.loc.x, loc.loc.y, loc.dir.index());
         LocationHistory(v)
     }
 
     fn push(&mut self, loc: Vector) -> Result<(), GoError> {
-        if !self.0.insert(loc) {
+        if !self.0.insert(loc.loc.x, loc.loc.y, loc.dir.index()) {
             Err(GoError::StuckInLoop(loc))
         } else {
             Ok(())
@@ -30,11 +30,7 @@ impl LocationHistory {
     }
 
     fn num_unique_locations(&self) -> usize {
-        self.0
-            .iter()
-            .map(|l| l.loc)
-            .collect::<HashSet<Location>>()
-            .len()
+        self.0.cells()
     }
 }
 
@@ -44,12 +40,36 @@ pub struct Vector {
     dir: Direction,
 }
 
-impl<'a> From<&'a Map> for Guard<'a> {
-    fn from(map: &Map) -> Guard {
+impl Vector {
+    /// where the guard starts on the given map
+    pub fn start(map: &Map) -> Vector {
         let loc = map.find_guard().expect("no guard found");
         let dir = map.char_at(&loc).into();
-        let current = Vector { loc, dir };
-        let history = LocationHistory::start(current);
+        Vector { loc, dir }
+    }
+
+    /// where the guard ends up after one step, turning if it's facing an obstacle
+    pub fn next(&self, map: &Map) -> Result<Vector, GoError> {
+        let new = self.loc.move_in(self.dir);
+
+        match map.try_char_at(new)? {
+            '#' => Ok(Vector {
+                loc: self.loc,
+                dir: self.dir.turn_right(),
+            }),
+            c if Guard::can_move_to(c) => Ok(Vector {
+                loc: new.try_into()?,
+                dir: self.dir,
+            }),
+            c => Err(GoError::UnknownChar(c)),
+        }
+    }
+}
+
+impl<'a> From<&'a Map> for Guard<'a> {
+    fn from(map: &Map) -> Guard<'_> {
+        let current = Vector::start(map);
+        let history = LocationHistory::start(current, map);
         Guard {
             current,
             history,
@@ -63,62 +83,58 @@ impl Guard<'_> {
         GUARD_CHARS.contains(c)
     }
 
-    fn turn(&mut self) -> Result<(), GoError> {
-        self.current.dir = match self.current.dir {
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-        };
-        self.history.push(self.current)?;
-        Ok(())
-    }
-
-    // takes a map, because we alter the map based on guard movements
-    // TODO: swap this around, just have the guard keep track of all previous locations,
-    // to be able to correctly throw "stuck in loop" error
-    // if location_history.contains(new_loc) { return Err(GoError::StuckInLoop) }
     pub fn step(&mut self) -> Result<(), GoError> {
-        let old = self.current.loc;
-        let new = old.move_in(self.current.dir);
-
-        match self.map.try_char_at(new)? {
-            '#' => {
-                self.turn()?;
-            }
-            c if self.can_move_to(c) => {
-                self.current.loc = new.try_into()?;
-                self.history.push(self.current)?;
-            }
-            c => return Err(GoError::UnknownChar(c)),
-        }
-
-        Ok(())
+        self.current = self.current.next(self.map)?;
+        self.history.push(self.current)
     }
 
-    fn can_move_to(&self, c: char) -> bool {
+    pub(crate) fn can_move_to(c: char) -> bool {
         CAN_MOVE.contains(&c) || Guard::is_guard(&c)
     }
 
     pub fn num_locations_visited(&self) -> usize {
         self.history.num_unique_locations()
     }
+
+    /// whether the guard ends up walking round in a loop on this map.
+    /// where it's been goes in `visited`, which is cleared first, so one
+    /// can be reused for every map that's checked
+    pub fn loops(map: &Map, visited: &mut Visited) -> bool {
+        visited.clear();
+        let mut current = Vector::start(map);
+        loop {
+            let Vector { loc, dir } = current;
+            if !visited.insert(loc.x, loc.y, dir.index()) {
+                return true;
+            }
+            match current.next(map) {
+                Ok(next) => current = next,
+                Err(_) => return false,
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn turn(guard: &mut Guard) -> Result<(), GoError> {
+        guard.current.dir = guard.current.dir.turn_right();
+        guard.history.push(guard.current)
+    }
+
     #[test]
     fn num_locations_visited() {
         let start = Vector {
             loc: Location { x: 0, y: 0 },
             dir: Direction::Right,
         };
+        let map = Map::from("...\n...\n...".to_string());
         let mut guard = Guard {
             current: start,
-            history: LocationHistory::start(start),
-            map: &mut Map::from("...\n...\n...".to_string()),
+            history: LocationHistory::start(start, &map),
+            map: &map,
         };
         assert_eq!(guard.num_locations_visited(), 1);
         guard.step().unwrap();
@@ -131,10 +147,11 @@ mod tests {
             loc: Location { x: 0, y: 0 },
             dir: Direction::Right,
         };
+        let map = Map::from("...\n...\n...".to_string());
         let mut guard = Guard {
             current: start,
-            history: LocationHistory::start(start),
-            map: &mut Map::from("...\n...\n...".to_string()),
+            history: LocationHistory::start(start, &map),
+            map: &map,
         };
         assert_eq!(guard.num_locations_visited(), 1);
         guard.step().unwrap();
@@ -156,23 +173,23 @@ mod tests {
             }
         );
 
-        guard.turn().unwrap();
+        turn(&mut guard).unwrap();
         assert_eq!(guard.num_locations_visited(), 3);
         assert_eq!(guard.history.0.len(), 4);
         assert_eq!(guard.current.dir, Direction::Down);
 
-        guard.turn().unwrap();
+        turn(&mut guard).unwrap();
         assert_eq!(guard.num_locations_visited(), 3);
         assert_eq!(guard.history.0.len(), 5);
         assert_eq!(guard.current.dir, Direction::Left);
 
-        guard.turn().unwrap();
+        turn(&mut guard).unwrap();
         assert_eq!(guard.num_locations_visited(), 3);
         assert_eq!(guard.history.0.len(), 6);
         assert_eq!(guard.current.dir, Direction::Up);
 
         assert_eq!(
-            guard.turn().unwrap_err(),
+            turn(&mut guard).unwrap_err(),
             GoError::StuckInLoop(Vector {
                 loc: Location { x: 2, y: 0 },
                 dir: Direction::Right
@@ -275,4 +292,15 @@ mod tests {
 
         assert_eq!(guard.num_locations_visited(), 41);
     }
+
+    #[test]
+    fn loops() {
+        let stuck = Map::from(".#..\n...#\n#^..\n..#.".to_string());
+        let free = Map::from(".#..\n...#\n.^..\n..#.".to_string());
+        let mut visited = Visited::new(4, 4, Direction::COUNT);
+        assert!(Guard::loops(&stuck, &mut visited));
+        // what the first walk left behind doesn't count against the next
+        assert!(!Guard::loops(&free, &mut visited));
+        assert!(Guard::loops(&stuck, &mut visited));
+    }
 }
@@ -1,53 +1,91 @@
 // for part 2, they ask where to put an obstacle such that the guard gets stuck in a loop
 
-use std::fs::File;
-
-use crate::{error::GoError, guard::Guard, location::Location, map::Map};
-
-pub fn solve() -> usize {
-    let mut file = File::open("input.txt").expect("File not found");
-    let mut map = Map::from(&mut file);
-    println!(
-        "Solving map with dimensions {}x{}",
-        map.width(),
-        map.height()
-    );
-    let solutions = find_places_that_create_loops(&mut map);
-    solutions.len()
+use utils::{
+    progress::{Cancelled, Progress},
+    visited::Visited,
+};
+
+use crate::{direction::Direction, guard::Guard, location::Location, map::Map};
+
+pub fn solve(
+    map: &mut Map,
+    progress: &mut dyn Progress,
+) -> Result<usize, Cancelled> {
+    Ok(find_places_that_create_loops(map, progress)?.len())
 }
 
-fn find_places_that_create_loops(map: &mut Map) -> Vec<Location> {
-    (0..map.height())
-        .flat_map(|y| {
-            (0..map.width())
-                .map(|x| Location { x, y })
-                .filter(|loc| loc_causes_loop(loc, map))
-                .collect::<Vec<Location>>()
-        })
-        .collect()
+// tries every cell in turn, reporting each one to `progress`
+fn find_places_that_create_loops(
+    map: &mut Map,
+    progress: &mut dyn Progress,
+) -> Result<Vec<Location>, Cancelled> {
+    let total = map.width() * map.height();
+    let mut res = Vec::new();
+    // shared by every candidate, rather than allocated for each one
+    let mut visited = Visited::new(map.width(), map.height(), Direction::COUNT);
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            let loc = Location { x, y };
+            if loc_causes_loop(&loc, map, &mut visited) {
+                res.push(loc);
+            }
+            progress.update(y * map.width() + x + 1, total)?;
+        }
+    }
+    Ok(res)
 }
 
-fn loc_causes_loop(loc: &Location, map: &mut Map) -> bool {
+fn loc_causes_loop(
+    loc: &Location,
+    map: &mut Map,
+    visited: &mut Visited,
+) -> bool {
     let c = map.char_at(loc);
     if Guard::is_guard(&c) || c == '#' {
         return false;
     }
     map.set_char_at(*loc, '#');
-    let res;
-    let mut guard = Guard::from(&*map);
-    loop {
-        match guard.step() {
-            Ok(()) => (),
-            Err(GoError::StuckInLoop(_)) => {
-                res = true;
-                break;
-            }
-            Err(_) => {
-                res = false;
-                break;
-            }
-        }
-    }
+    let res = Guard::loops(map, visited);
     map.set_char_at(*loc, c);
     res
 }
+
+#[cfg(test)]
+mod tests {
+    use utils::progress::Ignore;
+
+    use super::*;
+
+    #[test]
+    fn example() {
+        let mut map = Map::from(
+            r"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#..."
+                .to_string(),
+        );
+        let solutions =
+            find_places_that_create_loops(&mut map, &mut Ignore).unwrap();
+        assert_eq!(solutions.len(), 6);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut map = Map::from("....#\n.^...\n.....".to_string());
+        let mut updates = 0;
+        let res = solve(&mut map, &mut |done, total| {
+            updates += 1;
+            assert_eq!(total, 15);
+            done < 4
+        });
+        assert_eq!(res, Err(Cancelled { done: 4, total: 15 }));
+        assert_eq!(updates, 4);
+    }
+}
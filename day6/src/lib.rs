@@ -0,0 +1,35 @@
+mod direction;
+mod error;
+mod guard;
+mod hypothetical;
+mod location;
+mod map;
+
+pub use direction::Direction;
+pub use error::GoError;
+pub use guard::{Guard, Vector};
+pub use location::{Location, MaybeLocation};
+pub use map::Map;
+use utils::progress::{Cancelled, Ignore, Progress};
+
+pub fn parse(input: &str) -> Map {
+    Map::from(input.to_string())
+}
+
+pub fn p1(map: &Map) -> usize {
+    let mut guard = Guard::from(map);
+    while guard.step().is_ok() {}
+    guard.num_locations_visited()
+}
+
+pub fn p2(map: &Map) -> usize {
+    p2_with_progress(map, &mut Ignore).expect("ignoring progress never cancels")
+}
+
+/// `p2`, reporting each cell it tries an obstacle in
+pub fn p2_with_progress(
+    map: &Map,
+    progress: &mut dyn Progress,
+) -> Result<usize, Cancelled> {
+    hypothetical::solve(&mut map.clone(), progress)
+}
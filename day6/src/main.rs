@@ -1,14 +1,6 @@
-use std::{fs::File, time::Instant};
+use std::time::Instant;
 
-mod direction;
-mod error;
-mod guard;
-mod hypothetical;
-mod location;
-mod map;
-
-use guard::Guard;
-use map::Map;
+use day6::{p2, parse, Guard, Map};
 
 fn main() {
     let map = load_map();
@@ -21,10 +13,14 @@ fn main() {
     }
     println!("Part 1: {}", guard.num_locations_visited());
     let start = Instant::now();
-    println!("Part 2: {} in {:?}", hypothetical::solve(), start.elapsed());
+    println!(
+        "Solving map with dimensions {}x{}",
+        map.width(),
+        map.height()
+    );
+    println!("Part 2: {} in {:?}", p2(&map), start.elapsed());
 }
 
 fn load_map() -> Map {
-    let mut file = File::open("input.txt").expect("File not found");
-    Map::from(&mut file)
+    parse(&utils::input::read("input.txt"))
 }
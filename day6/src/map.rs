@@ -5,24 +5,67 @@ use crate::error::GoError;
 use crate::guard::Guard;
 use crate::location::{Location, MaybeLocation};
 
+/// serialized as its grid, one string with a line per row
 #[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "String", try_from = "UncheckedMap")
+)]
 pub struct Map {
     data: HashMap<Location, char>,
     width: usize,
     height: usize,
 }
 
+/// a map as it's deserialized, before checking the guard can walk it
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(transparent)]
+struct UncheckedMap(String);
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedMap> for Map {
+    type Error = String;
+
+    fn try_from(UncheckedMap(map): UncheckedMap) -> Result<Map, String> {
+        let rows: Vec<&str> = map.lines().collect();
+        let width = rows.first().map_or(0, |r| r.chars().count());
+        if width == 0 {
+            return Err("the map is empty".to_string());
+        }
+        if let Some(y) = rows.iter().position(|r| r.chars().count() != width) {
+            return Err(format!(
+                "row {} isn't {} wide like the first",
+                y + 1,
+                width
+            ));
+        }
+        let cells = rows.iter().flat_map(|r| r.chars());
+        if let Some(c) =
+            cells.clone().find(|c| *c != '#' && !Guard::can_move_to(*c))
+        {
+            return Err(format!("{:?} isn't something on the map", c));
+        }
+        let guards = cells.filter(Guard::is_guard).count();
+        if guards != 1 {
+            return Err(format!("the map has {} guards, not one", guards));
+        }
+        Ok(Map::from(map))
+    }
+}
+
 impl From<&mut File> for Map {
     fn from(file: &mut File) -> Map {
         let mut contents = String::new();
         file.read_to_string(&mut contents).unwrap();
-        Map::from(contents)
+        Map::from(utils::input::normalize(&contents))
     }
 }
 
 impl From<String> for Map {
     fn from(s: String) -> Map {
-        let rows: Vec<&str> = s.split("\n").collect();
+        let rows: Vec<&str> = s.lines().collect();
         let data = rows
             .iter()
             .enumerate()
@@ -41,6 +84,19 @@ impl From<String> for Map {
     }
 }
 
+impl From<Map> for String {
+    fn from(map: Map) -> String {
+        (0..map.height)
+            .map(|y| {
+                (0..map.width)
+                    .map(|x| map.char_at(&Location { x, y }))
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
 impl Map {
     pub fn find_guard(&self) -> Option<Location> {
         self.data
@@ -137,6 +193,27 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let map = Map::from("..#\n^..".to_string());
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#""..#\n^..""#);
+        let map: Map = serde_json::from_str(&json).unwrap();
+        assert_eq!(map.char_at(&Location { x: 2, y: 0 }), '#');
+        assert_eq!(map.find_guard(), Some(Location { x: 0, y: 1 }));
+
+        for (json, error) in [
+            (r#""""#, "the map is empty"),
+            (r#""..#\n^.""#, "row 2 isn't 3 wide like the first"),
+            (r#""..#\n...""#, "the map has 0 guards, not one"),
+            (r#""..#\n^.?""#, "'?' isn't something on the map"),
+        ] {
+            let e = serde_json::from_str::<Map>(json).err().unwrap();
+            assert!(e.to_string().starts_with(error), "{}", e);
+        }
+    }
+
     #[test]
     fn char_at() {
         let map = Map::from("abc\ndef\nghi".to_string());
@@ -155,4 +232,10 @@ mod tests {
             assert_eq!(map.char_at(&loc), c);
         }
     }
+
+    #[test]
+    fn trailing_newline() {
+        let map = Map::from("..#\n.^.\n".to_string());
+        assert_eq!(String::from(map), "..#\n.^.");
+    }
 }
@@ -1,13 +1,7 @@
 use std::time::Duration;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use day7::{concat, Equation};
-
-// this is about 30x slower
-fn old_concat(a: i64, b: i64) -> i64 {
-    let c = a.to_string() + &b.to_string();
-    c.parse().unwrap()
-}
+use day7::{concat, old_concat, Equation};
 
 pub fn bench_concat(c: &mut Criterion) {
     c.bench_function("concat", |b| {
@@ -1,8 +1,39 @@
+use std::fmt::Display;
+
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "UncheckedEquation")
+)]
 pub struct Equation {
     pub res: i64,
     rhs: Vec<i64>,
 }
 
+/// an equation as it's deserialized, before checking it has numbers to
+/// put operators between
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedEquation {
+    res: i64,
+    rhs: Vec<i64>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedEquation> for Equation {
+    type Error = String;
+
+    fn try_from(eq: UncheckedEquation) -> Result<Equation, String> {
+        if eq.rhs.is_empty() {
+            return Err(format!("{} has no numbers to make it from", eq.res));
+        }
+        Ok(Equation {
+            res: eq.res,
+            rhs: eq.rhs,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Op {
     Add,
@@ -10,26 +41,65 @@ pub enum Op {
     Cat,
 }
 
+impl Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Op::Add => write!(f, "+"),
+            Op::Mul => write!(f, "*"),
+            Op::Cat => write!(f, "||"),
+        }
+    }
+}
+
+/// lists the operators that solve each solvable equation
+pub fn explain(data: &[Equation], ops: &[Op]) -> Vec<String> {
+    data.iter()
+        .filter_map(|e| e.solve(ops).map(|solution| e.describe(&solution)))
+        .collect()
+}
+
 pub fn compute(data: &[Equation], ops: &[Op]) -> i64 {
+    compute_with(data, ops, concat)
+}
+
+/// like `compute`, with a different implementation of `||`
+pub fn compute_with(
+    data: &[Equation],
+    ops: &[Op],
+    cat: fn(i64, i64) -> i64,
+) -> i64 {
     data.iter()
-        .filter(|e| e.has_solution(ops))
+        .filter(|e| e.solve_with(ops, cat).is_some())
         .map(|e| e.res)
         .sum()
 }
 
 impl Equation {
-    fn has_solution(&self, ops: &[Op]) -> bool {
-        self.solve(ops).is_some()
+    fn solve(&self, operators: &[Op]) -> Option<Vec<Op>> {
+        self.solve_with(operators, concat)
     }
 
-    fn solve(&self, operators: &[Op]) -> Option<Vec<Op>> {
+    fn solve_with(
+        &self,
+        operators: &[Op],
+        cat: fn(i64, i64) -> i64,
+    ) -> Option<Vec<Op>> {
         let solutions: Vec<Vec<Op>> = perms(operators, self.rhs.len() - 1);
         solutions
             .into_iter()
-            .find(|solution| self.apply(solution) == self.res)
+            .find(|solution| self.apply(solution, cat) == self.res)
     }
 
-    fn apply(&self, solution: &[Op]) -> i64 {
+    // e.g. "292 = 11 + 6 * 16 + 20"
+    fn describe(&self, solution: &[Op]) -> String {
+        let mut s = format!("{} = {}", self.res, self.rhs[0]);
+        for (op, r) in solution.iter().zip(&self.rhs[1..]) {
+            s.push_str(&format!(" {} {}", op, r));
+        }
+        s
+    }
+
+    fn apply(&self, solution: &[Op], cat: fn(i64, i64) -> i64) -> i64 {
         assert!(solution.len() == self.rhs.len() - 1);
         let init = self.rhs[0];
         solution.iter().enumerate().fold(init, |acc, (i, op)| {
@@ -37,7 +107,7 @@ impl Equation {
             match op {
                 Op::Add => acc + r,
                 Op::Mul => acc * r,
-                Op::Cat => concat(acc, r),
+                Op::Cat => cat(acc, r),
             }
         })
     }
@@ -63,11 +133,17 @@ pub fn concat(a: i64, b: i64) -> i64 {
     a * 10_i64.pow(n_digits) + b
 }
 
+/// the first version of `concat`, about 30x slower
+pub fn old_concat(a: i64, b: i64) -> i64 {
+    let c = a.to_string() + &b.to_string();
+    c.parse().unwrap()
+}
+
 impl From<&str> for Equation {
     fn from(s: &str) -> Self {
         let (lhs, rhs) = s.split_once(": ").unwrap();
         let res = lhs.parse().unwrap();
-        let rhs = rhs.split(" ").map(|s| s.parse().unwrap()).collect();
+        let rhs = rhs.split_whitespace().map(|s| s.parse().unwrap()).collect();
         Equation { res, rhs }
     }
 }
@@ -84,6 +160,21 @@ mod tests {
         assert_eq!(eq.rhs, vec![1, 2, 3]);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let json =
+            serde_json::to_string(&Equation::from("123: 1 2 3")).unwrap();
+        assert_eq!(json, r#"{"res":123,"rhs":[1,2,3]}"#);
+        let eq: Equation = serde_json::from_str(&json).unwrap();
+        assert_eq!(eq.res, 123);
+        assert_eq!(eq.rhs, vec![1, 2, 3]);
+
+        let json = r#"{"res":123,"rhs":[]}"#;
+        let e = serde_json::from_str::<Equation>(json).err().unwrap();
+        assert!(e.to_string().starts_with("123 has no numbers"), "{}", e);
+    }
+
     #[test]
     fn test_perms() {
         let ops = &[Op::Add, Op::Mul];
@@ -117,6 +208,26 @@ mod tests {
     fn test_concat() {
         assert_eq!(concat(123, 456), 123456);
         assert_eq!(concat(456, 123), 456123);
+        assert_eq!(concat(12, 0), old_concat(12, 0));
+        assert_eq!(concat(7, 10), old_concat(7, 10));
+    }
+
+    #[test]
+    fn test_explain() {
+        let data = [
+            Equation::from("190: 10 19"),
+            Equation::from("83: 17 5"),
+            Equation::from("156: 15 6"),
+            Equation::from("292: 11 6 16 20"),
+        ];
+        assert_eq!(
+            explain(&data, &[Op::Add, Op::Mul]),
+            vec!["190 = 10 * 19", "292 = 11 + 6 * 16 + 20"]
+        );
+        assert_eq!(
+            explain(&data, &[Op::Add, Op::Mul, Op::Cat]),
+            vec!["190 = 10 * 19", "156 = 15 || 6", "292 = 11 + 6 * 16 + 20"]
+        );
     }
 
     #[test]
@@ -125,8 +236,16 @@ mod tests {
             res: 123,
             rhs: vec![1, 2, 3],
         };
-        assert_eq!(eq.apply(&[Op::Add, Op::Add]), 6);
-        assert_eq!(eq.apply(&[Op::Add, Op::Mul]), 9);
-        assert_eq!(eq.apply(&[Op::Mul, Op::Add]), 5);
+        assert_eq!(eq.apply(&[Op::Add, Op::Add], concat), 6);
+        assert_eq!(eq.apply(&[Op::Add, Op::Mul], concat), 9);
+        assert_eq!(eq.apply(&[Op::Mul, Op::Add], concat), 5);
+        assert_eq!(eq.apply(&[Op::Cat, Op::Cat], old_concat), 123);
+    }
+
+    #[test]
+    fn test_trailing_newline() {
+        let equations = crate::parse("190: 10 19\n3267: 81 40 27\n");
+        assert_eq!(equations.len(), 2);
+        assert_eq!(crate::p1(&equations), 3457);
     }
 }
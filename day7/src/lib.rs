@@ -1,18 +1,18 @@
-use std::io::Read;
-
 mod equation;
 
-use equation::compute;
 pub use equation::concat;
+pub use equation::old_concat;
 pub use equation::perms;
 pub use equation::Equation;
-use equation::Op;
+pub use equation::Op;
+use equation::{compute, compute_with, explain};
 
 pub fn load_input() -> Vec<Equation> {
-    let mut file = std::fs::File::open("input.txt").unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
-    contents.split("\n").map(Equation::from).collect()
+    parse(&utils::input::read("input.txt"))
+}
+
+pub fn parse(input: &str) -> Vec<Equation> {
+    input.lines().map(Equation::from).collect()
 }
 
 pub fn p1(data: &[Equation]) -> i64 {
@@ -22,3 +22,16 @@ pub fn p1(data: &[Equation]) -> i64 {
 pub fn p2(data: &[Equation]) -> i64 {
     compute(data, &[Op::Add, Op::Mul, Op::Cat])
 }
+
+/// `p2` using `old_concat`, to check the two agree
+pub fn p2_old_concat(data: &[Equation]) -> i64 {
+    compute_with(data, &[Op::Add, Op::Mul, Op::Cat], old_concat)
+}
+
+pub fn explain_p1(data: &[Equation]) -> Vec<String> {
+    explain(data, &[Op::Add, Op::Mul])
+}
+
+pub fn explain_p2(data: &[Equation]) -> Vec<String> {
+    explain(data, &[Op::Add, Op::Mul, Op::Cat])
+}
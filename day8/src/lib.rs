@@ -2,7 +2,7 @@ mod loc;
 mod map;
 
 pub use loc::Loc;
-pub use map::load_input;
+pub use map::{load_input, parse, Map};
 
 const NON_ANTENNA_CHARS: [char; 2] = ['.', '#'];
 
@@ -40,4 +40,12 @@ mod tests {
         let locs = map.find_all_antinode_locs();
         assert_eq!(locs.len(), 34);
     }
+
+    #[test]
+    fn test_trailing_newline() {
+        let input = std::fs::read_to_string("test_input.txt").unwrap();
+        let map = parse(&format!("{}\n", input.trim_end()));
+        assert_eq!(map.find_first_antinode_locs().len(), 14);
+        assert_eq!(map.find_all_antinode_locs().len(), 34);
+    }
 }
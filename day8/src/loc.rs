@@ -2,6 +2,7 @@ use crate::Error;
 use std::{collections::HashSet, hash::Hash};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Loc {
     pub x: usize,
     pub y: usize,
@@ -85,6 +86,27 @@ pub fn get_first_antinode_locs(a: &AntennaPair) -> [Option<Loc>; 2] {
     ]
 }
 
+/// whether `c` is in line with both antennae and, unless `any_distance`,
+/// on one side of them and twice as far from one as from the other
+pub fn is_antinode(c: Loc, pair: &AntennaPair, any_distance: bool) -> bool {
+    let to_first = c.diff(&pair.first());
+    let to_second = c.diff(&pair.second());
+    if to_first.dx * to_second.dy != to_first.dy * to_second.dx {
+        return false;
+    }
+    if any_distance {
+        return true;
+    }
+    // in between the two antennae, the vectors point opposite ways
+    if to_first.dx * to_second.dx + to_first.dy * to_second.dy <= 0 {
+        return false;
+    }
+    // compare squared distances, so twice as far is four times as much
+    let d1 = to_first.dx.pow(2) + to_first.dy.pow(2);
+    let d2 = to_second.dx.pow(2) + to_second.dy.pow(2);
+    d1 == 4 * d2 || d2 == 4 * d1
+}
+
 pub fn get_all_antinode_locs_in_bounds(
     a: Loc,
     b: Loc,
@@ -143,6 +165,23 @@ mod tests {
         assert_eq!(e, Error::OutOfBounds);
     }
 
+    #[test]
+    fn test_is_antinode() {
+        let pair = AntennaPair::new(Loc { x: 4, y: 3 }, Loc { x: 5, y: 5 });
+        assert!(is_antinode(Loc { x: 3, y: 1 }, &pair, false));
+        assert!(is_antinode(Loc { x: 6, y: 7 }, &pair, false));
+        assert!(!is_antinode(Loc { x: 7, y: 9 }, &pair, false));
+        assert!(is_antinode(Loc { x: 7, y: 9 }, &pair, true));
+        assert!(is_antinode(Loc { x: 4, y: 3 }, &pair, true));
+        assert!(!is_antinode(Loc { x: 4, y: 4 }, &pair, true));
+
+        // a third of the way between them is twice as far from one, but
+        // isn't one side of them
+        let pair = AntennaPair::new(Loc { x: 0, y: 0 }, Loc { x: 0, y: 6 });
+        assert!(!is_antinode(Loc { x: 0, y: 2 }, &pair, false));
+        assert!(is_antinode(Loc { x: 0, y: 12 }, &pair, false));
+    }
+
     #[test]
     fn test_get_all_antinode_locs_in_bounds() {
         let a = Loc { x: 1, y: 1 };
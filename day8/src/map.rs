@@ -1,19 +1,20 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs::File,
-    io::Read,
-};
+use std::collections::{HashMap, HashSet};
 
 use day7::perms;
 
 use crate::{
     loc::{
-        get_all_antinode_locs_in_bounds, get_first_antinode_locs, AntennaPair,
-        Loc,
+        get_all_antinode_locs_in_bounds, get_first_antinode_locs, is_antinode,
+        AntennaPair, Loc,
     },
     NON_ANTENNA_CHARS,
 };
 
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "UncheckedMap")
+)]
 pub struct Map {
     // data: Vec<Vec<char>>,
     antennae: HashMap<char, Vec<Loc>>,
@@ -21,10 +22,50 @@ pub struct Map {
     height: usize,
 }
 
+// a map as it comes in, before checking its antennae are on it
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedMap {
+    antennae: HashMap<char, Vec<Loc>>,
+    width: usize,
+    height: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedMap> for Map {
+    type Error = String;
+
+    fn try_from(m: UncheckedMap) -> Result<Map, String> {
+        if m.width == 0 || m.height == 0 {
+            return Err("the map is empty".to_string());
+        }
+        for (c, locs) in &m.antennae {
+            if NON_ANTENNA_CHARS.contains(c) {
+                return Err(format!("{c:?} isn't an antenna"));
+            }
+            if let Some(l) =
+                locs.iter().find(|l| l.x >= m.width || l.y >= m.height)
+            {
+                return Err(format!(
+                    "antenna {c:?} at {:?} is off the {}x{} map",
+                    (l.x, l.y),
+                    m.width,
+                    m.height
+                ));
+            }
+        }
+        Ok(Map {
+            antennae: m.antennae,
+            width: m.width,
+            height: m.height,
+        })
+    }
+}
+
 impl From<String> for Map {
     fn from(s: String) -> Map {
         let data: Vec<Vec<char>> =
-            s.split("\n").map(|line| line.chars().collect()).collect();
+            s.lines().map(|line| line.chars().collect()).collect();
         let antennae = data
             .iter()
             .enumerate()
@@ -54,10 +95,11 @@ impl From<String> for Map {
 }
 
 pub fn load_input(filename: &str) -> Map {
-    let mut file = File::open(filename).expect("File not found");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
-    Map::from(contents)
+    parse(&utils::input::read(filename))
+}
+
+pub fn parse(input: &str) -> Map {
+    Map::from(input.to_string())
 }
 
 impl Map {
@@ -94,6 +136,17 @@ impl Map {
             .collect()
     }
 
+    /// checks every cell against the puzzle's definition of an antinode,
+    /// rather than working outwards from each pair. much slower, but it
+    /// makes a good reference for the two above
+    pub fn scan_antinode_locs(&self, any_distance: bool) -> HashSet<Loc> {
+        let pairs = self.find_pairs();
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| Loc { x, y }))
+            .filter(|c| pairs.iter().any(|p| is_antinode(*c, p, any_distance)))
+            .collect()
+    }
+
     /// returns a vector of pairs of locations of matching types
     fn find_pairs(&self) -> HashSet<AntennaPair> {
         fn find_pairs(antennae: &[Loc]) -> Vec<AntennaPair> {
@@ -149,6 +202,35 @@ mod tests {
         assert_eq!(map.antennae, expected);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let map = parse("..\n.a");
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(
+            json,
+            r#"{"antennae":{"a":[{"x":1,"y":1}]},"width":2,"height":2}"#
+        );
+        let back: Map = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.antennae, map.antennae);
+        assert_eq!((back.width, back.height), (2, 2));
+
+        for (json, err) in [
+            (
+                json.replace(r#""height":2"#, r#""height":0"#),
+                "the map is empty",
+            ),
+            (json.replace(r#""a":"#, r##""#":"##), "'#' isn't an antenna"),
+            (
+                json.replace(r#""width":2"#, r#""width":1"#),
+                "antenna 'a' at (1, 1) is off the 1x2 map",
+            ),
+        ] {
+            let e = serde_json::from_str::<Map>(&json).err().unwrap();
+            assert!(e.to_string().starts_with(err), "{}", e);
+        }
+    }
+
     #[test]
     fn test_pair_equality() {
         let a = AntennaPair::new(Loc { x: 1, y: 2 }, Loc { x: 3, y: 4 });
@@ -170,4 +252,14 @@ mod tests {
 
     #[test]
     fn test_is_antinode_loc_valid() {}
+
+    #[test]
+    fn test_scan_antinode_locs() {
+        let map = load_input("test_input.txt");
+        assert_eq!(
+            map.scan_antinode_locs(false),
+            map.find_first_antinode_locs()
+        );
+        assert_eq!(map.scan_antinode_locs(true), map.find_all_antinode_locs());
+    }
 }
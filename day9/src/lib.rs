@@ -18,13 +18,57 @@ pub mod p2 {
     }
 }
 
-struct Drive {
+/// the files on the drive, and how big it is
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "UncheckedDrive")
+)]
+pub struct Drive {
     files: Vec<File>,
     size: usize,
 }
 
+/// a drive as it's deserialized, before checking its files fit on it
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedDrive {
+    files: Vec<File>,
+    size: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedDrive> for Drive {
+    type Error = String;
+
+    fn try_from(drive: UncheckedDrive) -> Result<Drive, String> {
+        let mut files: Vec<&File> = drive.files.iter().collect();
+        files.sort_by_key(|f| f.location);
+        if let Some(f) = files.iter().find(|f| f.size == 0) {
+            return Err(format!("file {} is empty", f.id));
+        }
+        if let Some(pair) =
+            files.windows(2).find(|p| p[1].location <= p[0].end_index())
+        {
+            return Err(format!(
+                "files {} and {} overlap",
+                pair[0].id, pair[1].id
+            ));
+        }
+        if let Some(f) = files.last().filter(|f| f.end_index() >= drive.size) {
+            return Err(format!("file {} runs off the end of the drive", f.id));
+        }
+        Ok(Drive {
+            files: drive.files,
+            size: drive.size,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
-struct File {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct File {
     id: usize,
     location: usize,
     size: usize,
@@ -86,7 +130,7 @@ impl Drive {
 
     fn mapping(&self) -> Vec<Option<usize>> {
         let mut files = self.files.clone();
-        files.sort_by(|a, b| a.location.cmp(&b.location));
+        files.sort_by_key(|f| f.location);
         let size: usize = files.iter().map(|f| f.size).sum();
         let mut mapping = Vec::with_capacity(size);
         let mut maybe_last_file: Option<&File> = None;
@@ -111,7 +155,7 @@ impl Drive {
     // returns the index of the first empty space
     fn first_empty_space(&self, min_size: usize) -> Option<usize> {
         let mut files = self.files.clone();
-        files.sort_by(|a, b| a.location.cmp(&b.location));
+        files.sort_by_key(|f| f.location);
 
         let mut maybe_last_file: Option<File> = None;
         // todo could rewrite with indices
@@ -191,6 +235,29 @@ mod tests {
 
     const INPUT: &str = "2333133121414131402";
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&Drive::from("12345")).unwrap();
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"files":[{"id":0,"location":0,"size":1},"#,
+                r#"{"id":1,"location":3,"size":3},"#,
+                r#"{"id":2,"location":10,"size":5}],"size":15}"#
+            )
+        );
+        let d: Drive = serde_json::from_str(&json).unwrap();
+        assert_eq!(d.to_string(), "0..111....22222");
+
+        let overlapping = json.replace(r#""location":3"#, r#""location":8"#);
+        let e = serde_json::from_str::<Drive>(&overlapping).unwrap_err();
+        assert!(e.to_string().starts_with("files 1 and 2 overlap"), "{}", e);
+        let short = json.replace(r#""size":15"#, r#""size":14"#);
+        let e = serde_json::from_str::<Drive>(&short).unwrap_err();
+        assert!(e.to_string().starts_with("file 2 runs off"), "{}", e);
+    }
+
     #[test]
     fn test_map_drive() {
         let d = Drive::from(INPUT);
@@ -229,4 +296,11 @@ mod tests {
     fn test_solve_2() {
         assert_eq!(p2::solve(INPUT), 2858);
     }
+
+    #[test]
+    fn test_trailing_newline() {
+        let input = format!("{}\n", INPUT);
+        assert_eq!(p1::solve(&input), 1928);
+        assert_eq!(p2::solve(&input), 2858);
+    }
 }
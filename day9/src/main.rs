@@ -10,5 +10,5 @@ fn main() {
 }
 
 fn load_input(filename: &str) -> String {
-    std::fs::read_to_string(filename).unwrap()
+    utils::input::read(filename)
 }
@@ -0,0 +1,38 @@
+// counts allocations, for benchmarks. it only counts once a binary
+// installs it with #[global_allocator], which the runner does
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// the system allocator, counting every allocation and reallocation
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// allocations so far, across every thread
+pub fn allocations() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
@@ -0,0 +1,227 @@
+// runs every part against a whole directory of inputs, e.g. one per person
+// under `dir/day7/alice.txt`, to catch solutions that only work on the
+// input they were written for
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    days, output,
+    run::{self, Run},
+};
+
+/// how many times slower than the median run of a part counts as slow
+const SLOWDOWN: u64 = 10;
+/// anything quicker than this is never slow, it's just noise
+const NOT_SLOW_US: u64 = 1000;
+
+/// one part run against one input
+pub struct Entry {
+    /// the input's file name, without the .txt
+    pub input: String,
+    pub run: Run,
+    pub note: Option<String>,
+}
+
+/// the .txt files in `dir/dayN`, sorted
+pub fn inputs(dir: &Path, day: u8) -> Vec<PathBuf> {
+    let mut res: Vec<PathBuf> =
+        std::fs::read_dir(dir.join(format!("day{}", day)))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|e| e == "txt"))
+            .collect();
+    res.sort();
+    res
+}
+
+/// an input that can't be read is recorded as a failure of every part,
+/// and the rest of the inputs are still run
+pub fn batch(
+    dir: &Path,
+    day: Option<u8>,
+    timeout: Option<Duration>,
+) -> Vec<Entry> {
+    let mut res = Vec::new();
+    for solver in days::select(day, None) {
+        let mut entries = Vec::new();
+        for path in inputs(dir, solver.day) {
+            let run = match run::load_file(&path, false) {
+                Ok(input) => run::run_limited(
+                    solver,
+                    &input,
+                    false,
+                    timeout,
+                    &mut |_, _| {},
+                ),
+                Err(e) => Run {
+                    day: solver.day,
+                    part: solver.part,
+                    answer: String::new(),
+                    time_us: 0,
+                    input_hash: String::new(),
+                    explanation: Vec::new(),
+                    cached: false,
+                    failure: Some(e.to_string()),
+                },
+            };
+            entries.push(Entry {
+                input: path.file_stem().unwrap().to_string_lossy().to_string(),
+                run,
+                note: None,
+            });
+        }
+        add_notes(&mut entries);
+        res.extend(entries);
+    }
+    res
+}
+
+/// flags failures, and runs much slower than is usual for the part
+fn add_notes(entries: &mut [Entry]) {
+    let mut times: Vec<u64> = entries
+        .iter()
+        .filter(|e| e.run.failure.is_none())
+        .map(|e| e.run.time_us)
+        .collect();
+    times.sort();
+    let median = times.get(times.len() / 2).copied();
+
+    for entry in entries.iter_mut() {
+        entry.note = match (&entry.run.failure, median) {
+            (Some(failure), _) => Some(failure.clone()),
+            (None, Some(median))
+                if times.len() > 1
+                    && entry.run.time_us > NOT_SLOW_US
+                    && entry.run.time_us > SLOWDOWN * median =>
+            {
+                Some(format!(
+                    "slow, {}x the median",
+                    entry.run.time_us / median.max(1)
+                ))
+            }
+            _ => None,
+        };
+    }
+}
+
+/// the entries as a table with aligned columns
+pub fn table(entries: &[Entry]) -> String {
+    let mut rows = vec![vec![
+        "day".to_string(),
+        "part".to_string(),
+        "input".to_string(),
+        "answer".to_string(),
+        "time".to_string(),
+    ]];
+    for e in entries {
+        rows.push(vec![
+            e.run.day.to_string(),
+            e.run.part.to_string(),
+            e.input.clone(),
+            e.run.answer.clone(),
+            format!("{:?}", Duration::from_micros(e.run.time_us)),
+            e.note
+                .as_ref()
+                .map(|n| format!("! {}", n))
+                .unwrap_or_default(),
+        ]);
+    }
+    output::align(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    fn entry(input: &str, time_us: u64, failure: Option<&str>) -> Entry {
+        Entry {
+            input: input.to_string(),
+            run: Run {
+                day: 9,
+                part: 1,
+                answer: if failure.is_some() { "" } else { "1928" }.to_string(),
+                time_us,
+                input_hash: String::new(),
+                explanation: vec![],
+                cached: false,
+                failure: failure.map(ToString::to_string),
+            },
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_add_notes() {
+        let mut entries = vec![
+            entry("alice", 2000, None),
+            entry("bob", 2500, None),
+            entry("carol", 40_000, None),
+            entry("dave", 10, Some("panicked")),
+        ];
+        add_notes(&mut entries);
+        let notes: Vec<Option<&str>> =
+            entries.iter().map(|e| e.note.as_deref()).collect();
+        assert_eq!(
+            notes,
+            vec![None, None, Some("slow, 16x the median"), Some("panicked")]
+        );
+
+        // quick parts don't count as slow, however much slower they are
+        let mut entries =
+            vec![entry("alice", 2, None), entry("bob", 900, None)];
+        add_notes(&mut entries);
+        assert!(entries.iter().all(|e| e.note.is_none()));
+    }
+
+    #[test]
+    fn test_table() {
+        let mut entries = vec![
+            entry("alice", 2000, None),
+            entry("bob", 12, Some("timed out after 1s")),
+        ];
+        add_notes(&mut entries);
+        assert_eq!(
+            table(&entries),
+            "day  part  input  answer  time\n\
+             9    1     alice  1928    2ms\n\
+             9    1     bob            12µs  ! timed out after 1s\n"
+        );
+    }
+
+    #[test]
+    fn test_batch() {
+        let dir = TestDir::new("batch");
+        std::fs::create_dir_all(dir.join("day9")).unwrap();
+        std::fs::write(dir.join("day9/bob.txt"), "12345\n").unwrap();
+        std::fs::write(dir.join("day9/alice.txt"), "2333133121414131402\n")
+            .unwrap();
+        std::fs::write(dir.join("day9/notes.md"), "not an input").unwrap();
+        // not utf-8, so it can't be read
+        std::fs::write(dir.join("day9/ann.txt"), [0xff, 0xfe]).unwrap();
+
+        let entries = batch(&dir, Some(9), None);
+        let answers: Vec<(u8, &str, &str)> = entries
+            .iter()
+            .map(|e| (e.run.part, e.input.as_str(), e.run.answer.as_str()))
+            .collect();
+        assert_eq!(
+            answers,
+            vec![
+                (1, "alice", "1928"),
+                (1, "ann", ""),
+                (1, "bob", "60"),
+                (2, "alice", "2858"),
+                (2, "ann", ""),
+                (2, "bob", "132"),
+            ]
+        );
+        let note = entries[1].note.as_deref().unwrap();
+        assert!(note.contains("ann.txt: stream did not contain"), "{}", note);
+    }
+}
@@ -0,0 +1,165 @@
+// checks every part against the time budget it declares in `days`, on the
+// real inputs, so a part that's got much slower doesn't go unnoticed
+
+use std::{error::Error, path::Path, time::Duration};
+
+use crate::{
+    days, output,
+    run::{self, Run},
+};
+
+/// parts that report progress are given up on after this many times their
+/// budget, so a slow one still gets reported. any other part can't be
+/// stopped, and is always run to the end
+const GIVE_UP_AFTER: u32 = 5;
+
+/// one part's run, and what it was allowed
+pub struct Check {
+    pub run: Run,
+    pub budget: Duration,
+}
+
+impl Check {
+    pub fn over(&self) -> bool {
+        self.run.failure.is_some() || self.time() > self.budget
+    }
+
+    fn time(&self) -> Duration {
+        Duration::from_micros(self.run.time_us)
+    }
+
+    /// how much of its budget the part used, 1.0 being all of it
+    fn used(&self) -> f64 {
+        match self.run.failure {
+            Some(_) => f64::INFINITY,
+            None => self.time().as_secs_f64() / self.budget.as_secs_f64(),
+        }
+    }
+}
+
+/// runs each part that has an input, worst offenders first
+pub fn check(root: &Path, day: Option<u8>) -> Vec<Check> {
+    let mut res = Vec::new();
+    for solver in days::select(day, None) {
+        let input = match run::load_input(root, solver.day, false) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("skipping day {}: {}", solver.day, e);
+                continue;
+            }
+        };
+        let timeout = solver.budget * GIVE_UP_AFTER;
+        let run = run::run_limited(
+            solver,
+            &input,
+            false,
+            Some(timeout),
+            &mut |_, _| {},
+        );
+        res.push(Check {
+            run,
+            budget: solver.budget,
+        });
+    }
+    sort(&mut res);
+    res
+}
+
+fn sort(checks: &mut [Check]) {
+    checks.sort_by(|a, b| b.used().total_cmp(&a.used()));
+}
+
+pub fn table(checks: &[Check]) -> String {
+    let mut rows = vec![vec![
+        "day".to_string(),
+        "part".to_string(),
+        "time".to_string(),
+        "budget".to_string(),
+        "used".to_string(),
+    ]];
+    for c in checks {
+        let (time, used, note) = match &c.run.failure {
+            Some(failure) => (String::new(), String::new(), failure.clone()),
+            None => (
+                format!("{:?}", c.time()),
+                format!("{:.0}%", c.used() * 100.0),
+                if c.over() { "over budget" } else { "" }.to_string(),
+            ),
+        };
+        rows.push(vec![
+            c.run.day.to_string(),
+            c.run.part.to_string(),
+            time,
+            format!("{:?}", c.budget),
+            used,
+            note,
+        ]);
+    }
+    output::align(&rows)
+}
+
+/// prints the table, and fails if any part went over its budget
+pub fn report(checks: &[Check]) -> Result<(), Box<dyn Error>> {
+    if checks.is_empty() {
+        return Err("no inputs to check".into());
+    }
+    print!("{}", table(checks));
+    let over = checks.iter().filter(|c| c.over()).count();
+    if over > 0 {
+        return Err(format!("{} part(s) over budget", over).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    fn checked(part: u8, time_us: u64, failure: Option<&str>) -> Check {
+        Check {
+            run: Run {
+                day: 6,
+                part,
+                answer: String::new(),
+                time_us,
+                input_hash: String::new(),
+                explanation: vec![],
+                cached: false,
+                failure: failure.map(ToString::to_string),
+            },
+            budget: Duration::from_millis(10),
+        }
+    }
+
+    #[test]
+    fn test_table() {
+        let mut checks = vec![
+            checked(1, 2000, None),
+            checked(2, 15_000, None),
+            checked(2, 50_000, Some("timed out after 50ms")),
+        ];
+        sort(&mut checks);
+        assert!(checks.iter().map(|c| c.over()).eq([true, true, false]));
+        assert_eq!(
+            table(&checks),
+            "day  part  time  budget  used\n\
+             6    2           10ms          timed out after 50ms\n\
+             6    2     15ms  10ms    150%  over budget\n\
+             6    1     2ms   10ms    20%\n"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let root = TestDir::new("budget");
+        std::fs::create_dir_all(root.join("day9")).unwrap();
+        std::fs::write(root.join("day9/input.txt"), "2333133121414131402")
+            .unwrap();
+        let checks = check(&root, Some(9));
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(|c| !c.over()));
+        assert!(report(&checks).is_ok());
+        assert!(report(&check(&root, Some(8))).is_err());
+    }
+}
@@ -0,0 +1,263 @@
+// answers from earlier runs, so slow parts don't have to be solved again.
+// entries are keyed by the input and by the source the solver was built
+// from, so editing either one invalidates them without any bookkeeping
+
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    days::Solver,
+    run::{self, default_root, Run},
+};
+
+pub struct Cache {
+    path: PathBuf,
+    /// the workspace the solvers were built from
+    src_root: PathBuf,
+    entries: HashMap<String, Run>,
+    source_hashes: HashMap<u8, String>,
+}
+
+/// where the runner keeps its cache unless told otherwise
+pub fn default_path() -> PathBuf {
+    default_root().join("target").join("runner-cache.json")
+}
+
+impl Cache {
+    /// loads the cache at `path`. a missing or unreadable cache is
+    /// treated as empty, it'll be overwritten on the next save
+    pub fn open(path: &Path, src_root: &Path) -> Cache {
+        let entries = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Cache {
+            path: path.to_path_buf(),
+            src_root: src_root.to_path_buf(),
+            entries,
+            source_hashes: HashMap::new(),
+        }
+    }
+
+    pub fn get(&mut self, day: u8, part: u8, input_hash: &str) -> Option<Run> {
+        let key = self.key(day, part, input_hash);
+        let mut run = self.entries.get(&key)?.clone();
+        run.cached = true;
+        Some(run)
+    }
+
+    /// remembers the answer, unless there isn't one
+    pub fn insert(&mut self, run: &Run) {
+        if run.failure.is_some() {
+            return;
+        }
+        let key = self.key(run.day, run.part, &run.input_hash);
+        let run = Run {
+            explanation: Vec::new(),
+            cached: false,
+            ..run.clone()
+        };
+        self.entries.insert(key, run);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string(&self.entries)?)
+    }
+
+    fn key(&mut self, day: u8, part: u8, input_hash: &str) -> String {
+        let src_root = &self.src_root;
+        let source_hash = self
+            .source_hashes
+            .entry(day)
+            .or_insert_with(|| source_hash(src_root, day));
+        format!("{}/{}/{}/{}", day, part, input_hash, source_hash)
+    }
+}
+
+/// answers from the cache when it can, and otherwise with `solve`,
+/// caching what it comes up with. explaining always solves the part
+/// again, since explanations aren't cached
+pub fn run(
+    cache: &mut Cache,
+    solver: &Solver,
+    input: &str,
+    explain: bool,
+    solve: impl FnOnce() -> Run,
+) -> Run {
+    if !explain {
+        if let Some(run) = cache.get(solver.day, solver.part, &run::hash(input))
+        {
+            return run;
+        }
+    }
+    let run = solve();
+    cache.insert(&run);
+    run
+}
+
+/// every file a day's answers could depend on: its own crate, every crate
+/// it depends on by path, the lock file pinning the rest, and the runner's
+/// wrappers around it
+pub fn source_files(src_root: &Path, day: u8) -> Vec<PathBuf> {
+    let mut files = vec![
+        src_root.join("Cargo.lock"),
+        src_root.join("runner/src/days.rs"),
+    ];
+    let mut crates = vec![src_root.join(format!("day{}", day))];
+    let mut seen = HashSet::new();
+    while let Some(dir) = crates.pop() {
+        let dir = dir.canonicalize().unwrap_or(dir);
+        if !seen.insert(dir.clone()) {
+            continue;
+        }
+        files.push(dir.join("Cargo.toml"));
+        collect_files(&dir.join("src"), &mut files);
+        crates.extend(path_dependencies(&dir));
+    }
+    files.sort();
+    files
+}
+
+/// every file under `dir`, however deep
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// the crates the one in `dir` depends on by path
+fn path_dependencies(dir: &Path) -> Vec<PathBuf> {
+    let Some(manifest) = std::fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|m| m.parse::<toml::Table>().ok())
+    else {
+        return Vec::new();
+    };
+    manifest
+        .get("dependencies")
+        .and_then(|d| d.as_table())
+        .into_iter()
+        .flat_map(|deps| deps.values())
+        .filter_map(|dep| dep.get("path")?.as_str())
+        .map(|path| dir.join(path))
+        .collect()
+}
+
+/// hash of every file in `source_files`
+fn source_hash(src_root: &Path, day: u8) -> String {
+    let mut hasher = Sha256::new();
+    for file in source_files(src_root, day) {
+        hasher.update(file.to_string_lossy().as_bytes());
+        if let Ok(contents) = std::fs::read(&file) {
+            hasher.update(&contents);
+        }
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    fn workspace(name: &str) -> TestDir {
+        let root = TestDir::new(name);
+        std::fs::create_dir_all(root.join("day9/src")).unwrap();
+        std::fs::write(root.join("day9/src/lib.rs"), "fn p1() {}").unwrap();
+        root
+    }
+
+    fn run() -> Run {
+        Run {
+            day: 9,
+            part: 1,
+            answer: "1928".to_string(),
+            time_us: 4000,
+            input_hash: "abc".to_string(),
+            explanation: vec!["moved 9 first".to_string()],
+            cached: false,
+            failure: None,
+        }
+    }
+
+    #[test]
+    fn test_get() {
+        let root = workspace("cache_get");
+        let path = root.join("cache.json");
+
+        let mut cache = Cache::open(&path, &root);
+        assert_eq!(cache.get(9, 1, "abc"), None);
+        cache.insert(&run());
+        cache.save().unwrap();
+
+        let mut cache = Cache::open(&path, &root);
+        let cached = cache.get(9, 1, "abc").unwrap();
+        assert!(cached.cached);
+        assert_eq!(cached.answer, "1928");
+        assert!(cached.explanation.is_empty());
+        assert_eq!(cache.get(9, 2, "abc"), None);
+        assert_eq!(cache.get(9, 1, "abd"), None);
+
+        cache.insert(&Run {
+            part: 2,
+            failure: Some("timed out after 1s".to_string()),
+            ..run()
+        });
+        assert_eq!(cache.get(9, 2, "abc"), None);
+    }
+
+    #[test]
+    fn test_source_change() {
+        let root = workspace("cache_source_change");
+        let before = source_hash(&root, 9);
+        assert_eq!(source_hash(&root, 9), before);
+        std::fs::write(root.join("day9/src/lib.rs"), "fn p1() { 1 }").unwrap();
+        assert_ne!(source_hash(&root, 9), before);
+        assert_ne!(source_hash(&root, 8), before);
+    }
+
+    #[test]
+    fn test_dependency_change() {
+        let root = workspace("cache_dependency");
+        std::fs::create_dir_all(root.join("day10/src")).unwrap();
+        std::fs::write(
+            root.join("day10/Cargo.toml"),
+            "[dependencies]\nday9 = { path = \"../day9\" }\n",
+        )
+        .unwrap();
+        let before = source_hash(&root, 10);
+        std::fs::write(root.join("day9/src/lib.rs"), "fn p1() { 2 }").unwrap();
+        let after = source_hash(&root, 10);
+        assert_ne!(after, before);
+        std::fs::write(root.join("Cargo.lock"), "version = 4").unwrap();
+        assert_ne!(source_hash(&root, 10), after);
+    }
+
+    #[test]
+    fn test_source_files() {
+        let root = default_root().canonicalize().unwrap();
+        let files = source_files(&root, 8);
+        assert!(files.contains(&root.join("day8/src/lib.rs")));
+        assert!(files.contains(&root.join("day7/src/lib.rs")));
+        assert!(files.contains(&root.join("utils/src/lib.rs")));
+        assert!(files.contains(&root.join("Cargo.lock")));
+        assert!(!files.contains(&root.join("day9/src/lib.rs")));
+    }
+}
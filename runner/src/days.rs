@@ -0,0 +1,405 @@
+// every part of every day, wrapped so they all take the raw puzzle input
+
+use aoc2024::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10,
+    day11, day12, day13, day14,
+};
+use std::time::Duration;
+
+use utils::{
+    answer::Answer,
+    progress::{Cancelled, Progress},
+};
+
+/// how long most parts should take on a real input, at most
+pub const BUDGET: Duration = Duration::from_secs(1);
+
+/// solves a part while telling `Progress` how it's getting on
+pub type SolveWithProgress =
+    fn(&str, &mut dyn Progress) -> Result<Answer, Cancelled>;
+
+#[derive(Clone, Copy)]
+pub struct Solver {
+    pub day: u8,
+    pub part: u8,
+    pub solve: fn(&str) -> Answer,
+    /// describes how the answer was reached, for days that support it
+    pub explain: Option<fn(&str) -> Vec<String>>,
+    /// solves the part while reporting how far it's got, for slow parts
+    pub progress: Option<SolveWithProgress>,
+    /// how long it should take on a real input, at most
+    pub budget: Duration,
+}
+
+pub const SOLVERS: &[Solver] = &[
+    Solver {
+        day: 1,
+        part: 1,
+        solve: |input| {
+            let (l1, l2) = day01::parse(input);
+            day01::p1(&l1, &l2).into()
+        },
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 1,
+        part: 2,
+        solve: |input| {
+            let (l1, l2) = day01::parse(input);
+            day01::p2(&l1, &l2).into()
+        },
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 2,
+        part: 1,
+        solve: |input| day02::p1(&day02::parse(input)).into(),
+        explain: Some(|input| day02::explain_p1(&day02::parse(input))),
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 2,
+        part: 2,
+        solve: |input| day02::p2(&day02::parse(input)).into(),
+        explain: Some(|input| day02::explain_p2(&day02::parse(input))),
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 3,
+        part: 1,
+        solve: |input| day03::p1(input).into(),
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 3,
+        part: 2,
+        solve: |input| day03::p2(input).into(),
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 4,
+        part: 1,
+        solve: |input| day04::p1(day04::parse(input)).into(),
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 4,
+        part: 2,
+        solve: |input| day04::p2(day04::parse(input)).into(),
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 5,
+        part: 1,
+        solve: |input| {
+            let (rules, updates) = day05::parse(input);
+            day05::p1(&rules, &updates).into()
+        },
+        explain: Some(|input| {
+            let (rules, updates) = day05::parse(input);
+            day05::explain_p1(&rules, &updates)
+        }),
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 5,
+        part: 2,
+        solve: |input| {
+            let (rules, updates) = day05::parse(input);
+            day05::p2(&rules, &updates).into()
+        },
+        explain: Some(|input| {
+            let (rules, updates) = day05::parse(input);
+            day05::explain_p2(&rules, &updates)
+        }),
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 6,
+        part: 1,
+        solve: |input| day06::p1(&day06::parse(input)).into(),
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 6,
+        part: 2,
+        solve: |input| day06::p2(&day06::parse(input)).into(),
+        explain: None,
+        progress: Some(|input, progress| {
+            day06::p2_with_progress(&day06::parse(input), progress)
+                .map(|n| n.into())
+        }),
+        // tries an obstacle in every cell the guard could reach
+        budget: Duration::from_secs(10),
+    },
+    Solver {
+        day: 7,
+        part: 1,
+        solve: |input| day07::p1(&day07::parse(input)).into(),
+        explain: Some(|input| day07::explain_p1(&day07::parse(input))),
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 7,
+        part: 2,
+        solve: |input| day07::p2(&day07::parse(input)).into(),
+        explain: Some(|input| day07::explain_p2(&day07::parse(input))),
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 8,
+        part: 1,
+        solve: |input| {
+            day08::parse(input).find_first_antinode_locs().len().into()
+        },
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 8,
+        part: 2,
+        solve: |input| {
+            day08::parse(input).find_all_antinode_locs().len().into()
+        },
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 9,
+        part: 1,
+        solve: |input| day09::p1::solve(input).into(),
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 9,
+        part: 2,
+        solve: |input| day09::p2::solve(input).into(),
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 10,
+        part: 1,
+        solve: |input| day10::solve(input).into(),
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 10,
+        part: 2,
+        solve: |input| day10::solve_p2(input).into(),
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 11,
+        part: 1,
+        solve: |input| day11::p1::solve(input).into(),
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 11,
+        part: 2,
+        solve: |input| day11::p2::solve(input).into(),
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 12,
+        part: 1,
+        solve: |input| day12::p1::solve(input).into(),
+        explain: Some(day12::p1::explain),
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 12,
+        part: 2,
+        solve: |input| day12::p2::solve(input).into(),
+        explain: Some(day12::p2::explain),
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 13,
+        part: 1,
+        solve: |input| day13::p1(&day13::parse(input)).into(),
+        explain: Some(|input| day13::explain_p1(&day13::parse(input))),
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 13,
+        part: 2,
+        solve: |input| {
+            let mut machines = day13::parse(input);
+            day13::modify_input(&mut machines);
+            day13::p2(&machines).into()
+        },
+        explain: Some(|input| {
+            let mut machines = day13::parse(input);
+            day13::modify_input(&mut machines);
+            day13::explain_p2(&machines)
+        }),
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 14,
+        part: 1,
+        solve: |input| {
+            day14::p1::run(day14::parse(input), day14::BOUNDS).into()
+        },
+        explain: None,
+        progress: None,
+        budget: BUDGET,
+    },
+    Solver {
+        day: 14,
+        part: 2,
+        solve: |input| {
+            day14::p2::first_tree(day14::parse(input), day14::BOUNDS).into()
+        },
+        explain: Some(|input| {
+            let mut robots = day14::parse(input);
+            let steps = day14::p2::first_tree(robots.clone(), day14::BOUNDS);
+            robots.iter_mut().for_each(|r| r.step(steps, day14::BOUNDS));
+            let map = day14::ee::Map {
+                locs: robots.iter().map(day14::Robot::loc).collect(),
+                bounds: day14::BOUNDS,
+            };
+            let picture = Answer::art(&map.to_string()).to_string();
+            let mut res = vec![format!("after {} seconds:", steps)];
+            res.extend(picture.lines().map(str::to_string));
+            res
+        }),
+        progress: Some(|input, progress| {
+            let robots = day14::parse(input);
+            day14::p2::first_tree_with_progress(robots, day14::BOUNDS, progress)
+                .map(Answer::from)
+        }),
+        budget: BUDGET,
+    },
+];
+
+/// another way of solving a part, which should always agree with the
+/// `Solver` for it
+pub struct Variant {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: fn(&str) -> Answer,
+}
+
+pub const VARIANTS: &[Variant] = &[
+    Variant {
+        day: 7,
+        part: 2,
+        name: "old_concat",
+        solve: |input| day07::p2_old_concat(&day07::parse(input)).into(),
+    },
+    Variant {
+        day: 8,
+        part: 1,
+        name: "scan",
+        solve: |input| {
+            day08::parse(input).scan_antinode_locs(false).len().into()
+        },
+    },
+    Variant {
+        day: 8,
+        part: 2,
+        name: "scan",
+        solve: |input| {
+            day08::parse(input).scan_antinode_locs(true).len().into()
+        },
+    },
+    Variant {
+        day: 11,
+        part: 1,
+        name: "field",
+        solve: |input| day11::p2::count(input, 25).into(),
+    },
+];
+
+/// the other ways of solving a part
+pub fn variants(day: u8, part: u8) -> Vec<&'static Variant> {
+    VARIANTS
+        .iter()
+        .filter(|v| v.day == day && v.part == part)
+        .collect()
+}
+
+/// the solvers to run, optionally only for one day and/or part
+pub fn select(day: Option<u8>, part: Option<u8>) -> Vec<&'static Solver> {
+    SOLVERS
+        .iter()
+        .filter(|s| day.is_none_or(|d| d == s.day))
+        .filter(|s| part.is_none_or(|p| p == s.part))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_day_has_two_parts() {
+        for day in 1..=14 {
+            let parts: Vec<u8> =
+                select(Some(day), None).iter().map(|s| s.part).collect();
+            assert_eq!(parts, vec![1, 2], "day {}", day);
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        let day9 = select(Some(9), None);
+        assert_eq!((day9[0].solve)("2333133121414131402"), Answer::Int(1928));
+        assert_eq!((day9[1].solve)("2333133121414131402"), Answer::Int(2858));
+
+        let day3 = select(Some(3), Some(1));
+        let input =
+            "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        assert_eq!((day3[0].solve)(input), Answer::Int(161));
+    }
+
+    #[test]
+    fn test_variants_have_a_solver() {
+        for v in VARIANTS {
+            assert_eq!(select(Some(v.day), Some(v.part)).len(), 1);
+        }
+        assert_eq!(variants(8, 2).len(), 1);
+        assert!(variants(9, 1).is_empty());
+    }
+}
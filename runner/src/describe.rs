@@ -0,0 +1,288 @@
+// statistics about an input, without solving it: how big it is, what's in
+// it, and the few things about particular days that decide which approach
+// is fast enough. malformed inputs tend to stand out here too
+
+use std::collections::HashMap;
+
+use crate::output;
+
+/// a statistic's name and value
+pub type Stat = (String, String);
+
+/// days whose inputs are lists of numbers, even when every line's the same
+/// width and they look like grids
+const LIST_DAYS: [u8; 7] = [1, 2, 5, 7, 11, 13, 14];
+/// days whose inputs are grids, even when a malformed one isn't
+const GRID_DAYS: [u8; 5] = [4, 6, 8, 10, 12];
+
+pub fn describe(day: u8, input: &str) -> Vec<Stat> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut res = vec![stat("lines", lines.len())];
+    let rows: Vec<Vec<char>> =
+        lines.iter().map(|l| l.chars().collect()).collect();
+    let grid = if LIST_DAYS.contains(&day) {
+        false
+    } else {
+        GRID_DAYS.contains(&day) || is_grid(&rows)
+    };
+    if grid {
+        res.push(stat("grid", grid_size(&rows)));
+        res.push(stat("chars", histogram(&rows)));
+    } else {
+        res.extend(number_stats(&lines));
+    }
+    res.extend(match day {
+        7 => day7(&lines),
+        11 => day11(&lines),
+        13 => day13(input),
+        _ => vec![],
+    });
+    res
+}
+
+/// the stats, one to a line with their values lined up
+pub fn format(stats: &[Stat]) -> String {
+    let rows: Vec<Vec<String>> = stats
+        .iter()
+        .map(|(name, value)| vec![format!("{}:", name), value.clone()])
+        .collect();
+    output::align(&rows)
+}
+
+fn stat(name: &str, value: impl ToString) -> Stat {
+    (name.to_string(), value.to_string())
+}
+
+/// at least two rows, all the same width
+fn is_grid(rows: &[Vec<char>]) -> bool {
+    rows.len() > 1
+        && rows[0].len() > 1
+        && rows.iter().all(|r| r.len() == rows[0].len())
+}
+
+/// width x height, or the range of widths if the rows aren't all the same
+fn grid_size(rows: &[Vec<char>]) -> String {
+    if is_grid(rows) {
+        return format!("{} x {}", rows[0].len(), rows.len());
+    }
+    let widths = rows.iter().map(Vec::len);
+    format!(
+        "ragged, {} to {} wide",
+        widths.clone().min().unwrap_or(0),
+        widths.max().unwrap_or(0)
+    )
+}
+
+/// how often each char appears, most common first
+fn histogram(rows: &[Vec<char>]) -> String {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in rows.iter().flatten() {
+        *counts.entry(*c).or_default() += 1;
+    }
+    let mut counts: Vec<(char, usize)> = counts.into_iter().collect();
+    counts.sort_by_key(|(c, n)| (std::cmp::Reverse(*n), *c));
+    counts
+        .iter()
+        .map(|(c, n)| format!("{:?} {}", c, n))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// every integer in the line, e.g. 94 and -3 in "X+94, Y=-3"
+pub(crate) fn numbers(line: &str) -> Vec<i64> {
+    let mut res = Vec::new();
+    let mut current = String::new();
+    for c in line.chars().chain(std::iter::once(' ')) {
+        if c.is_ascii_digit() || (c == '-' && current.is_empty()) {
+            current.push(c);
+        } else {
+            if let Ok(n) = current.parse() {
+                res.push(n);
+            }
+            current.clear();
+            if c == '-' {
+                current.push(c);
+            }
+        }
+    }
+    res
+}
+
+fn number_stats(lines: &[&str]) -> Vec<Stat> {
+    let per_line: Vec<Vec<i64>> = lines.iter().map(|l| numbers(l)).collect();
+    let all: Vec<i64> = per_line.iter().flatten().copied().collect();
+    let (Some(min), Some(max)) = (all.iter().min(), all.iter().max()) else {
+        return vec![];
+    };
+    let counts: Vec<usize> = per_line
+        .iter()
+        .filter(|ns| !ns.is_empty())
+        .map(Vec::len)
+        .collect();
+    vec![
+        stat("numbers", format!("{}, from {} to {}", all.len(), min, max)),
+        stat(
+            "per line",
+            format!(
+                "{} to {}",
+                counts.iter().min().unwrap(),
+                counts.iter().max().unwrap()
+            ),
+        ),
+    ]
+}
+
+fn day7(lines: &[&str]) -> Vec<Stat> {
+    let longest = lines
+        .iter()
+        .enumerate()
+        .max_by_key(|(i, l)| (numbers(l).len(), std::cmp::Reverse(*i)));
+    match longest {
+        Some((i, line)) => vec![stat(
+            "longest equation",
+            format!(
+                "{} numbers on the right, line {}",
+                numbers(line).len().saturating_sub(1),
+                i + 1
+            ),
+        )],
+        None => vec![],
+    }
+}
+
+fn day11(lines: &[&str]) -> Vec<Stat> {
+    let stones: Vec<i64> = lines.iter().flat_map(|l| numbers(l)).collect();
+    match stones.iter().max() {
+        Some(largest) => vec![
+            stat("stones", stones.len()),
+            stat(
+                "largest stone",
+                format!("{}, {} digits", largest, largest.to_string().len()),
+            ),
+        ],
+        None => vec![],
+    }
+}
+
+/// machines whose buttons move in the same direction have no single
+/// solution, and need handling separately
+fn day13(input: &str) -> Vec<Stat> {
+    let machines: Vec<Vec<i64>> = input
+        .split("\n\n")
+        .map(|block| block.lines().flat_map(numbers).collect())
+        .collect();
+    let zero = machines
+        .iter()
+        .filter(|m| m.len() == 6 && m[0] * m[3] - m[1] * m[2] == 0)
+        .count();
+    let malformed = machines.iter().filter(|m| m.len() != 6).count();
+    let mut res = vec![
+        stat("machines", machines.len()),
+        stat("zero determinant", zero),
+    ];
+    if malformed > 0 {
+        res.push(stat("malformed machines", malformed));
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names_and_values(stats: &[Stat]) -> Vec<(&str, &str)> {
+        stats
+            .iter()
+            .map(|(n, v)| (n.as_str(), v.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(numbers("p=0,4 v=3,-3"), vec![0, 4, 3, -3]);
+        assert_eq!(numbers("Button A: X+94, Y+34"), vec![94, 34]);
+        assert_eq!(numbers("190: 10 19"), vec![190, 10, 19]);
+        assert_eq!(numbers("mul(2,4)-"), vec![2, 4]);
+        assert_eq!(numbers("..#"), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn test_grid() {
+        let stats = describe(6, "..#.\n.^..\n#...");
+        assert_eq!(
+            names_and_values(&stats),
+            vec![
+                ("lines", "3"),
+                ("grid", "4 x 3"),
+                ("chars", "'.' 9, '#' 2, '^' 1"),
+            ]
+        );
+
+        let stats = describe(6, "..#.\n.^\n#...");
+        assert_eq!(stats[1], stat("grid", "ragged, 2 to 4 wide"));
+    }
+
+    #[test]
+    fn test_lists() {
+        // every line's the same width, but it's still a list
+        let stats = describe(1, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3");
+        assert_eq!(
+            names_and_values(&stats),
+            vec![
+                ("lines", "6"),
+                ("numbers", "12, from 1 to 9"),
+                ("per line", "2 to 2"),
+            ]
+        );
+
+        let stats = describe(2, "7 6 4 2 1\n1 2 7 8 9\n9 7 6");
+        assert_eq!(
+            names_and_values(&stats),
+            vec![
+                ("lines", "3"),
+                ("numbers", "13, from 1 to 9"),
+                ("per line", "3 to 5"),
+            ]
+        );
+
+        let stats = describe(7, "190: 10 19\n3267: 81 40 27\n83: 17 5");
+        assert_eq!(
+            stats.last().unwrap(),
+            &stat("longest equation", "3 numbers on the right, line 2")
+        );
+
+        let stats = describe(11, "125 17 2024");
+        assert_eq!(
+            names_and_values(&stats[3..]),
+            vec![("stones", "3"), ("largest stone", "2024, 4 digits")]
+        );
+    }
+
+    #[test]
+    fn test_day13() {
+        let input = "Button A: X+94, Y+34\n\
+                     Button B: X+22, Y+67\n\
+                     Prize: X=8400, Y=5400\n\
+                     \n\
+                     Button A: X+2, Y+4\n\
+                     Button B: X+3, Y+6\n\
+                     Prize: X=10, Y=20\n\
+                     \n\
+                     Button A: X+2\n";
+        let stats = describe(13, input);
+        assert_eq!(
+            names_and_values(&stats[3..]),
+            vec![
+                ("machines", "3"),
+                ("zero determinant", "1"),
+                ("malformed machines", "1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let stats = vec![stat("lines", 3), stat("grid", "4 x 3")];
+        assert_eq!(format(&stats), "lines:  3\ngrid:   4 x 3\n");
+    }
+}
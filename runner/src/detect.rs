@@ -0,0 +1,299 @@
+// guesses which day an input is for from its shape, e.g. `p=..v=..` lines
+// are day 14's robots. each day scores how well the input fits what its
+// parser expects, from 0 for not at all to 1 for exactly
+
+use crate::{describe::numbers, output};
+
+/// how well an input fits a day's parser
+struct Shape {
+    day: u8,
+    score: fn(&str) -> f64,
+}
+
+/// a score at least this high, ahead of every other day, is a match
+const CONFIDENT: f64 = 0.9;
+/// how many days to list when there's no clear match
+const CLOSEST: usize = 5;
+
+const SHAPES: &[Shape] = &[
+    Shape {
+        day: 1,
+        score: |input| {
+            lines_matching(input, |l| {
+                // two columns, three spaces apart
+                let tokens: Vec<&str> = l.split("   ").collect();
+                tokens.len() == 2 && all_numbers(&tokens)
+            })
+        },
+    },
+    Shape {
+        day: 2,
+        score: |input| {
+            let fit = lines_matching(input, |l| {
+                let tokens: Vec<&str> = l.split(' ').collect();
+                tokens.len() > 2 && all_numbers(&tokens)
+            });
+            // a single line of numbers is more likely day 11's stones
+            if input.lines().count() > 1 {
+                fit
+            } else {
+                fit / 2.0
+            }
+        },
+    },
+    Shape {
+        day: 3,
+        score: |input| if input.contains("mul(") { 1.0 } else { 0.0 },
+    },
+    Shape {
+        day: 4,
+        score: |input| grid_of(input, |c| "XMAS".contains(c)),
+    },
+    Shape {
+        day: 5,
+        score: |input| {
+            let Some((rules, updates)) = input.split_once("\n\n") else {
+                return 0.0;
+            };
+            let rules = lines_matching(rules, |l| {
+                l.split_once('|').is_some_and(|(a, b)| all_numbers(&[a, b]))
+            });
+            let updates = lines_matching(updates, |l| {
+                all_numbers(&l.split(',').collect::<Vec<&str>>())
+            });
+            (rules + updates) / 2.0
+        },
+    },
+    Shape {
+        day: 6,
+        score: |input| {
+            let guards = input.chars().filter(|c| "^v<>".contains(*c)).count();
+            let fit = grid_of(input, |c| ".#^v<>".contains(c));
+            if guards == 1 {
+                fit
+            } else {
+                fit / 2.0
+            }
+        },
+    },
+    Shape {
+        day: 7,
+        score: |input| {
+            lines_matching(input, |l| {
+                l.split_once(": ").is_some_and(|(res, rhs)| {
+                    let rhs: Vec<&str> = rhs.split(' ').collect();
+                    all_numbers(&[res]) && all_numbers(&rhs)
+                })
+            })
+        },
+    },
+    Shape {
+        day: 8,
+        score: |input| {
+            let fit = grid_of(input, |c| c == '.' || c.is_ascii_alphanumeric());
+            if mostly(input, |c| c == '.') {
+                fit
+            } else {
+                fit / 2.0
+            }
+        },
+    },
+    Shape {
+        day: 9,
+        score: |input| {
+            let digits =
+                !input.is_empty() && input.chars().all(|c| c.is_ascii_digit());
+            if digits {
+                1.0
+            } else {
+                0.0
+            }
+        },
+    },
+    Shape {
+        day: 10,
+        score: |input| {
+            let fit = grid_of(input, |c| c == '.' || c.is_ascii_digit());
+            if mostly(input, |c| c.is_ascii_digit()) {
+                fit
+            } else {
+                fit / 2.0
+            }
+        },
+    },
+    Shape {
+        day: 11,
+        score: |input| {
+            let stones: Vec<&str> = input.split(' ').collect();
+            match (input.lines().count(), stones.len()) {
+                (1, 1) if all_numbers(&stones) => 0.5,
+                (1, _) if all_numbers(&stones) => 1.0,
+                _ => 0.0,
+            }
+        },
+    },
+    Shape {
+        day: 12,
+        score: |input| {
+            let fit = grid_of(input, |c| c.is_ascii_uppercase());
+            // a grid of just these is more likely day 4's word search
+            if input
+                .lines()
+                .flat_map(str::chars)
+                .all(|c| "XMAS".contains(c))
+            {
+                fit / 2.0
+            } else {
+                fit
+            }
+        },
+    },
+    Shape {
+        day: 13,
+        score: |input| {
+            lines_matching(input, |l| {
+                let prefix = ["Button A: ", "Button B: ", "Prize: "]
+                    .iter()
+                    .any(|p| l.starts_with(p));
+                prefix && numbers(l).len() == 2
+            })
+        },
+    },
+    Shape {
+        day: 14,
+        score: |input| {
+            lines_matching(input, |l| {
+                l.starts_with("p=")
+                    && l.contains(" v=")
+                    && numbers(l).len() == 4
+            })
+        },
+    },
+];
+
+fn all_numbers(tokens: &[&str]) -> bool {
+    tokens.iter().all(|t| t.parse::<u64>().is_ok())
+}
+
+/// the fraction of the non-blank lines for which `f` holds
+fn lines_matching(input: &str, f: impl Fn(&str) -> bool) -> f64 {
+    let lines: Vec<&str> =
+        input.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.is_empty() {
+        return 0.0;
+    }
+    lines.iter().filter(|l| f(l)).count() as f64 / lines.len() as f64
+}
+
+/// if the input's a grid, the fraction of its cells for which `f` holds
+fn grid_of(input: &str, f: impl Fn(char) -> bool) -> f64 {
+    let rows: Vec<&str> = input.lines().collect();
+    let width = rows.first().map_or(0, |r| r.chars().count());
+    let grid = rows.len() > 1
+        && width > 1
+        && rows.iter().all(|r| r.chars().count() == width);
+    if !grid {
+        return 0.0;
+    }
+    let cells = rows.iter().flat_map(|r| r.chars());
+    cells.filter(|c| f(*c)).count() as f64 / (width * rows.len()) as f64
+}
+
+/// whether `f` holds for at least half the chars
+fn mostly(input: &str, f: impl Fn(char) -> bool) -> bool {
+    let chars = input.chars().filter(|c| !c.is_whitespace());
+    let (matching, total) =
+        chars.fold((0, 0), |(m, t), c| (m + f(c) as usize, t + 1));
+    total > 0 && matching * 2 >= total
+}
+
+/// every day the input could be for, most likely first
+pub fn detect(input: &str) -> Vec<(u8, f64)> {
+    let mut res: Vec<(u8, f64)> = SHAPES
+        .iter()
+        .map(|s| (s.day, (s.score)(input)))
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+    res.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    res
+}
+
+/// the day, if one stands out from the rest
+pub fn likely_day(ranked: &[(u8, f64)]) -> Option<u8> {
+    match ranked {
+        [(day, best), rest @ ..]
+            if *best >= CONFIDENT
+                && rest.first().is_none_or(|r| r.1 < *best) =>
+        {
+            Some(*day)
+        }
+        _ => None,
+    }
+}
+
+pub fn report(ranked: &[(u8, f64)]) -> String {
+    if let Some(day) = likely_day(ranked) {
+        return format!("day {}\n", day);
+    }
+    if ranked.is_empty() {
+        return "doesn't look like any day\n".to_string();
+    }
+    let rows: Vec<Vec<String>> = ranked
+        .iter()
+        .take(CLOSEST)
+        .map(|(day, score)| {
+            vec![format!("day {}", day), format!("{:.0}%", score * 100.0)]
+        })
+        .collect();
+    format!("not sure, the closest are:\n{}", output::align(&rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run, watch};
+
+    fn likely(input: &str) -> Option<u8> {
+        likely_day(&detect(input))
+    }
+
+    #[test]
+    fn test_fixtures() {
+        let root = run::default_root();
+        for day in 1..=14 {
+            for fixture in watch::fixtures(&root, day) {
+                let input = run::load_file(&fixture, false).unwrap();
+                assert_eq!(likely(&input), Some(day), "{}", fixture.display());
+            }
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        let examples = [
+            (1, "3   4\n4   3\n2   5"),
+            (2, "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1"),
+            (3, "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)"),
+            (5, "47|53\n97|13\n\n75,47,61,53,29\n97,61,53"),
+            (6, "....#.....\n.........#\n....^.....\n#........."),
+            (7, "190: 10 19\n3267: 81 40 27\n83: 17 5"),
+            (9, "2333133121414131402"),
+            (10, "0123\n1234\n8765\n9876"),
+            (11, "125 17"),
+            (11, "0 7 198844 5687836 58 2 816 3"),
+        ];
+        for (day, input) in examples {
+            assert_eq!(likely(input), Some(day), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        assert_eq!(report(&detect("p=0,4 v=3,-3\np=6,3 v=-1,-3")), "day 14\n");
+        assert_eq!(report(&detect("")), "doesn't look like any day\n");
+        assert_eq!(
+            report(&detect("1   2\nhello world")),
+            "not sure, the closest are:\nday 1  50%\n"
+        );
+    }
+}
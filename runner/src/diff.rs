@@ -0,0 +1,318 @@
+// differential testing: every variant of a part is run against the part's
+// solver on its fixtures and on generated inputs, and any disagreement is
+// minimized down to something small enough to debug by hand
+
+use std::{error::Error, path::Path};
+
+use aoc2024::day07;
+use utils::answer::Answer;
+
+use crate::{
+    days::{self, Solver, Variant},
+    minimize::minimize,
+    panics, run, watch,
+};
+
+/// what a solver made of an input, or its panic message
+pub(crate) type Outcome = Result<String, String>;
+
+/// an input the solver and one of its variants disagree on
+#[derive(Debug, PartialEq)]
+pub struct Disagreement {
+    pub input: String,
+    pub expected: Outcome,
+    pub actual: Outcome,
+}
+
+/// how one variant fared against its part's solver
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub variant: &'static str,
+    pub inputs: usize,
+    pub disagreements: usize,
+    /// the first disagreement found, minimized
+    pub smallest: Option<Disagreement>,
+}
+
+pub fn diff(
+    root: &Path,
+    day: Option<u8>,
+    cases: usize,
+    seed: u64,
+) -> Result<(), Box<dyn Error>> {
+    let mut reports = Vec::new();
+    for solver in days::select(day, None) {
+        let variants = days::variants(solver.day, solver.part);
+        if variants.is_empty() {
+            continue;
+        }
+        let inputs = inputs(root, solver.day, cases, seed)?;
+        for variant in variants {
+            reports.push(check(solver, variant, &inputs));
+        }
+    }
+
+    if reports.is_empty() {
+        return Err("no variants to compare".into());
+    }
+    for report in &reports {
+        print!("{}", describe(report));
+    }
+    let failed = reports.iter().filter(|r| r.disagreements > 0).count();
+    if failed > 0 {
+        return Err(format!("{} variant(s) disagree", failed).into());
+    }
+    Ok(())
+}
+
+/// the day's fixtures followed by `cases` generated inputs
+fn inputs(
+    root: &Path,
+    day: u8,
+    cases: usize,
+    seed: u64,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut res = Vec::new();
+    for fixture in watch::fixtures(root, day) {
+        res.push(run::load_file(&fixture, false)?);
+    }
+    let mut rng = Rng::new(seed);
+    res.extend((0..cases).filter_map(|_| generate(day, &mut rng)));
+    Ok(res)
+}
+
+pub fn check(solver: &Solver, variant: &Variant, inputs: &[String]) -> Report {
+    let disagreements: Vec<Disagreement> = inputs
+        .iter()
+        .filter_map(|input| compare(solver, variant, input))
+        .collect();
+    let smallest = disagreements.first().map(|d| {
+        let input = minimize(&d.input, still_disagrees(solver, variant, d));
+        compare(solver, variant, &input).unwrap()
+    });
+    Report {
+        day: solver.day,
+        part: solver.part,
+        variant: variant.name,
+        inputs: inputs.len(),
+        disagreements: disagreements.len(),
+        smallest,
+    }
+}
+
+/// whether an input is another example of `original`: the solver and
+/// variant come up with the same answers, or panic with the same messages
+pub(crate) fn still_disagrees<'a>(
+    solver: &'a Solver,
+    variant: &'a Variant,
+    original: &'a Disagreement,
+) -> impl Fn(&str) -> bool + 'a {
+    move |input| {
+        compare(solver, variant, input).is_some_and(|d| {
+            d.expected == original.expected && d.actual == original.actual
+        })
+    }
+}
+
+pub(crate) fn compare(
+    solver: &Solver,
+    variant: &Variant,
+    input: &str,
+) -> Option<Disagreement> {
+    let expected = attempt(solver.solve, input);
+    let actual = attempt(variant.solve, input);
+    (expected != actual).then(|| Disagreement {
+        input: input.to_string(),
+        expected,
+        actual,
+    })
+}
+
+/// solvers are expected to panic on some of the inputs thrown at them,
+/// which are caught without being printed
+pub(crate) fn attempt(solve: fn(&str) -> Answer, input: &str) -> Outcome {
+    panics::catch(|| solve(input))
+        .map_err(|panic| panic.message)
+        .and_then(|answer| answer.submission())
+}
+
+fn describe(report: &Report) -> String {
+    let mut s = format!(
+        "day {} part {}: {} ",
+        report.day, report.part, report.variant
+    );
+    let Some(d) = &report.smallest else {
+        s.push_str(&format!("agrees on all {} inputs\n", report.inputs));
+        return s;
+    };
+    s.push_str(&format!(
+        "disagrees on {} of {} inputs, smallest:\n",
+        report.disagreements, report.inputs
+    ));
+    for line in d.input.lines() {
+        s.push_str(&format!("    {}\n", line));
+    }
+    let show = |o: &Outcome| match o {
+        Ok(answer) => answer.clone(),
+        Err(message) => format!("panic ({})", message),
+    };
+    s.push_str(&format!(
+        "  expected {}, got {}\n",
+        show(&d.expected),
+        show(&d.actual)
+    ));
+    s
+}
+
+/// a random input for the day, for days that have variants
+fn generate(day: u8, rng: &mut Rng) -> Option<String> {
+    match day {
+        7 => Some(
+            (0..rng.between(1, 4))
+                .map(|_| {
+                    let rhs: Vec<i64> = (0..rng.between(2, 4))
+                        .map(|_| rng.between(1, 20) as i64)
+                        .collect();
+                    // about half the equations should be solvable
+                    let res = if rng.between(0, 1) == 0 {
+                        rhs[1..].iter().fold(rhs[0], |acc, r| {
+                            match rng.between(0, 2) {
+                                0 => acc + r,
+                                1 => acc * r,
+                                _ => day07::concat(acc, *r),
+                            }
+                        })
+                    } else {
+                        rng.between(1, 1000) as i64
+                    };
+                    let rhs: Vec<String> =
+                        rhs.iter().map(ToString::to_string).collect();
+                    format!("{}: {}", res, rhs.join(" "))
+                })
+                .collect::<Vec<String>>()
+                .join("\n"),
+        ),
+        8 => {
+            let width = rng.between(4, 10) as usize;
+            let height = rng.between(4, 10) as usize;
+            let mut grid = vec![vec!['.'; width]; height];
+            for _ in 0..rng.between(2, 6) {
+                let x = rng.between(0, width as u64 - 1) as usize;
+                let y = rng.between(0, height as u64 - 1) as usize;
+                grid[y][x] = ['a', 'A', '0'][rng.between(0, 2) as usize];
+            }
+            let rows: Vec<String> =
+                grid.iter().map(|row| row.iter().collect()).collect();
+            Some(rows.join("\n"))
+        }
+        11 => {
+            let stones: Vec<String> = (0..rng.between(1, 3))
+                .map(|_| rng.between(0, 999).to_string())
+                .collect();
+            Some(stones.join(" "))
+        }
+        _ => None,
+    }
+}
+
+/// xorshift, so a seed always generates the same inputs
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        Rng(seed.max(1))
+    }
+
+    /// a number from `lo` to `hi`, inclusive
+    fn between(&mut self, lo: u64, hi: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        lo + self.0 % (hi - lo + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2024::day09;
+
+    fn solver(day: u8, part: u8) -> &'static Solver {
+        days::select(Some(day), Some(part))[0]
+    }
+
+    #[test]
+    fn test_agree() {
+        let variant = days::variants(7, 2)[0];
+        let mut rng = Rng::new(1);
+        let inputs: Vec<String> =
+            (0..20).filter_map(|_| generate(7, &mut rng)).collect();
+        let report = check(solver(7, 2), variant, &inputs);
+        assert_eq!(report.inputs, 20);
+        assert_eq!(report.disagreements, 0);
+        assert_eq!(report.smallest, None);
+    }
+
+    #[test]
+    fn test_disagree() {
+        let variant = Variant {
+            day: 9,
+            part: 1,
+            name: "broken",
+            solve: |input| {
+                if input.contains('7') {
+                    panic!("can't handle 7s");
+                }
+                day09::p1::solve(input).into()
+            },
+        };
+        let inputs = vec![
+            "12345".to_string(),
+            "17171".to_string(),
+            "2333133121414131402".to_string(),
+            "707".to_string(),
+        ];
+        let report = check(solver(9, 1), &variant, &inputs);
+        assert_eq!(report.disagreements, 2);
+        assert_eq!(
+            report.smallest,
+            Some(Disagreement {
+                input: "17171".to_string(),
+                expected: Ok("4".to_string()),
+                actual: Err("can't handle 7s".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let report = Report {
+            day: 8,
+            part: 2,
+            variant: "scan",
+            inputs: 30,
+            disagreements: 2,
+            smallest: Some(Disagreement {
+                input: "a..\n...\n..a".to_string(),
+                expected: Ok("2".to_string()),
+                actual: Err("oops".to_string()),
+            }),
+        };
+        assert_eq!(
+            describe(&report),
+            "day 8 part 2: scan disagrees on 2 of 30 inputs, smallest:\n    a..\n    ...\n    ..a\n  expected 2, got panic (oops)\n"
+        );
+    }
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        for _ in 0..100 {
+            let n = a.between(3, 5);
+            assert!((3..=5).contains(&n));
+            assert_eq!(n, b.between(3, 5));
+        }
+    }
+}
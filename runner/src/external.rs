@@ -0,0 +1,273 @@
+// solutions from outside this repo, e.g. in another language, checked
+// against ours. an external solver is any command that reads a puzzle
+// input on stdin and prints one answer per line, part 1 first. a blank
+// line is a part it has no answer for
+
+use std::{
+    error::Error,
+    io::{Read, Write},
+    path::Path,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    days, output,
+    run::{self, input_path, Run},
+    watch,
+};
+
+/// what the external solver made of one input
+pub struct Theirs {
+    pub answers: Vec<String>,
+    pub time: Duration,
+}
+
+/// both solvers run against one input
+pub struct Comparison {
+    /// the input's file name
+    pub input: String,
+    pub ours: Vec<Run>,
+    pub theirs: Result<Theirs, String>,
+}
+
+impl Comparison {
+    fn agrees(&self) -> bool {
+        self.theirs.as_ref().is_ok_and(|theirs| {
+            self.ours.iter().enumerate().all(|(i, run)| {
+                theirs.answers.get(i).is_some_and(|a| *a == run.answer)
+            })
+        })
+    }
+}
+
+/// how often to check whether the external solver has finished
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// runs the command with the input on its stdin, killing it if it's still
+/// going after `timeout`
+pub fn solve(
+    command: &[String],
+    input: &str,
+    timeout: Duration,
+) -> Result<Theirs, String> {
+    let (program, args) = command.split_first().ok_or("no command given")?;
+    let start = Instant::now();
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("couldn't start {}: {}", program, e))?;
+    // written and read on their own threads, so that neither side blocks
+    // on a full pipe while the other's waiting for it. if it exits without
+    // reading its input, the write fails, but the exit status below is the
+    // more useful thing to report
+    let mut stdin = child.stdin.take().unwrap();
+    let input = format!("{}\n", input);
+    thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let read = |mut pipe: Box<dyn Read + Send>| {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = pipe.read_to_end(&mut buf);
+            String::from_utf8_lossy(&buf).to_string()
+        })
+    };
+    let stdout = read(Box::new(child.stdout.take().unwrap()));
+    let stderr = read(Box::new(child.stderr.take().unwrap()));
+
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            break status;
+        }
+        if start.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("timed out after {:?}", timeout));
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    let time = start.elapsed();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if !status.success() {
+        return Err(format!("{}: {}", status, stderr.trim()));
+    }
+    // by position, so a blank part 1 doesn't move part 2 into its place
+    let answers = stdout.lines().map(|l| l.trim().to_string()).collect();
+    Ok(Theirs { answers, time })
+}
+
+/// the day's fixtures and input.txt, each run through our solvers and
+/// through `command`, giving up on either after `timeout`
+pub fn compare(
+    root: &Path,
+    day: u8,
+    command: &[String],
+    timeout: Duration,
+) -> Result<Vec<Comparison>, Box<dyn Error>> {
+    let mut paths = watch::fixtures(root, day);
+    paths.push(input_path(root, day));
+    let solvers = days::select(Some(day), None);
+    if solvers.is_empty() {
+        return Err(format!("day {} isn't solved", day).into());
+    }
+
+    let mut res = Vec::new();
+    for path in paths.iter().filter(|p| p.exists()) {
+        let input = run::load_file(path, false)?;
+        res.push(Comparison {
+            input: path.file_name().unwrap().to_string_lossy().to_string(),
+            ours: solvers
+                .iter()
+                .map(|s| {
+                    run::run_limited(
+                        s,
+                        &input,
+                        false,
+                        Some(timeout),
+                        &mut |_, _| {},
+                    )
+                })
+                .collect(),
+            theirs: solve(command, &input, timeout),
+        });
+    }
+    Ok(res)
+}
+
+/// answers and times side by side, marking any disagreement
+pub fn table(comparisons: &[Comparison]) -> String {
+    let mut rows = vec![vec![
+        "input".to_string(),
+        "part".to_string(),
+        "ours".to_string(),
+        "theirs".to_string(),
+    ]];
+    for c in comparisons {
+        let theirs = match &c.theirs {
+            Ok(theirs) => theirs,
+            Err(e) => {
+                rows.push(vec![
+                    c.input.clone(),
+                    "".into(),
+                    "".into(),
+                    e.clone(),
+                ]);
+                continue;
+            }
+        };
+        for (i, run) in c.ours.iter().enumerate() {
+            let answer = theirs.answers.get(i).cloned().unwrap_or_default();
+            let mark = if answer == run.answer { "" } else { "!" };
+            rows.push(vec![
+                c.input.clone(),
+                run.part.to_string(),
+                run.answer.clone(),
+                answer,
+                mark.to_string(),
+            ]);
+        }
+        let ours: u64 = c.ours.iter().map(|r| r.time_us).sum();
+        rows.push(vec![
+            c.input.clone(),
+            "time".to_string(),
+            format!("{:?}", Duration::from_micros(ours)),
+            format!("{:?}", theirs.time),
+        ]);
+    }
+    output::align(&rows)
+}
+
+/// prints the table, and fails if the solvers disagree on anything
+pub fn report(comparisons: &[Comparison]) -> Result<(), Box<dyn Error>> {
+    print!("{}", table(comparisons));
+    let failed = comparisons.iter().filter(|c| !c.agrees()).count();
+    if failed > 0 {
+        return Err(format!("disagreed on {} input(s)", failed).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    fn sh(script: &str) -> Vec<String> {
+        vec!["sh".to_string(), "-c".to_string(), script.to_string()]
+    }
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn test_solve() {
+        let theirs = solve(&sh("wc -c; echo 2"), "12345", TIMEOUT).unwrap();
+        assert_eq!(theirs.answers, vec!["6", "2"]);
+        // no answer for part 1 doesn't make part 2's answer part 1's
+        let theirs = solve(&sh("echo; echo 2"), "", TIMEOUT).unwrap();
+        assert_eq!(theirs.answers, vec!["", "2"]);
+
+        let err = solve(&sh("echo oops >&2; exit 3"), "", TIMEOUT)
+            .err()
+            .unwrap();
+        assert!(err.ends_with(": oops"), "{}", err);
+        assert!(solve(&["/no/such/solver".to_string()], "", TIMEOUT).is_err());
+    }
+
+    #[test]
+    fn test_solve_big_input() {
+        // more than a pipe holds, in both directions at once
+        let input = "1".repeat(1 << 20);
+        let theirs = solve(&sh("cat; echo"), &input, TIMEOUT).unwrap();
+        assert_eq!(theirs.answers[0].len(), 1 << 20);
+    }
+
+    #[test]
+    fn test_solve_timeout() {
+        let start = Instant::now();
+        let timeout = Duration::from_millis(100);
+        let err = solve(&sh("sleep 10"), "", timeout).err().unwrap();
+        assert!(err.starts_with("timed out"), "{}", err);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_compare() {
+        let root = TestDir::new("external");
+        std::fs::create_dir_all(root.join("day9")).unwrap();
+        std::fs::write(root.join("day9/test_input.txt"), "2333133121414131402")
+            .unwrap();
+
+        let comparisons = compare(
+            &root,
+            9,
+            &sh("cat > /dev/null; echo 1928; echo 2858"),
+            TIMEOUT,
+        )
+        .unwrap();
+        assert_eq!(comparisons.len(), 1);
+        assert!(comparisons[0].agrees());
+
+        let comparisons = compare(
+            &root,
+            9,
+            &sh("cat > /dev/null; echo 1928; echo 42"),
+            TIMEOUT,
+        )
+        .unwrap();
+        assert!(!comparisons[0].agrees());
+        // the widths depend on how long the solvers took
+        let table = table(&comparisons);
+        let rows: Vec<Vec<&str>> = table
+            .lines()
+            .map(|l| l.split_whitespace().collect())
+            .collect();
+        assert_eq!(rows[0], vec!["input", "part", "ours", "theirs"]);
+        assert_eq!(rows[1], vec!["test_input.txt", "1", "1928", "1928"]);
+        assert_eq!(rows[2], vec!["test_input.txt", "2", "2858", "42", "!"]);
+        assert_eq!(rows[3][..2], ["test_input.txt", "time"]);
+    }
+}
@@ -0,0 +1,337 @@
+// benchmark results kept across runs, one json line per part per run, so
+// a part that's got slower since its best run can be spotted. unlike
+// criterion's results, every entry records the commit it was measured at
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    allocs,
+    days::{self, Solver},
+    output,
+    panics::{self, Panic},
+    run,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// short hash, with -dirty on the end if there were local changes
+    pub commit: String,
+    /// seconds since the epoch
+    pub timestamp: u64,
+    pub day: u8,
+    pub part: u8,
+    pub input_hash: String,
+    pub median_us: u64,
+    /// how many times one solve allocated, or reallocated
+    pub allocations: usize,
+}
+
+/// where the history is kept unless told otherwise. timings only mean
+/// anything on the machine they were taken on, so it isn't committed
+pub fn default_path(root: &Path) -> PathBuf {
+    root.join("bench-history.jsonl")
+}
+
+/// the commit checked out in `root`
+pub fn commit(root: &Path) -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) => match git(&["status", "--porcelain", "-uno"]) {
+            Some(changes) if !changes.is_empty() => format!("{}-dirty", hash),
+            _ => hash,
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+/// the median time of `samples` solves, after one to warm up, and how
+/// many allocations a solve made
+pub fn measure(
+    solver: &Solver,
+    input: &str,
+    samples: usize,
+) -> Result<(u64, usize), Panic> {
+    panics::catch(|| (solver.solve)(input))?;
+    let mut times = Vec::new();
+    let mut allocations = 0;
+    for _ in 0..samples.max(1) {
+        let before = allocs::allocations();
+        let start = Instant::now();
+        (solver.solve)(input);
+        times.push(start.elapsed().as_micros() as u64);
+        allocations = allocs::allocations() - before;
+    }
+    times.sort();
+    Ok((times[times.len() / 2], allocations))
+}
+
+/// benchmarks each part that has an input and doesn't panic on it
+pub fn bench(root: &Path, day: Option<u8>, samples: usize) -> Vec<Entry> {
+    let commit = commit(root);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let mut res = Vec::new();
+    for solver in days::select(day, None) {
+        let input = match run::load_input(root, solver.day, false) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("skipping day {}: {}", solver.day, e);
+                continue;
+            }
+        };
+        let (median_us, allocations) = match measure(solver, &input, samples) {
+            Ok(measured) => measured,
+            Err(panic) => {
+                eprintln!(
+                    "skipping day {} part {}: {}",
+                    solver.day, solver.part, panic
+                );
+                continue;
+            }
+        };
+        res.push(Entry {
+            commit: commit.clone(),
+            timestamp,
+            day: solver.day,
+            part: solver.part,
+            input_hash: run::hash(&input),
+            median_us,
+            allocations,
+        });
+    }
+    res
+}
+
+pub fn append(path: &Path, entries: &[Entry]) -> io::Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    for entry in entries {
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    }
+    Ok(())
+}
+
+/// the history, oldest first. a missing file is an empty history
+pub fn load(path: &Path) -> Result<Vec<Entry>, Box<dyn Error>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("{}: {}", path.display(), e).into()),
+    };
+    let mut res = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let entry = serde_json::from_str(line)
+            .map_err(|e| format!("{}:{}: {}", path.display(), i + 1, e))?;
+        res.push(entry);
+    }
+    Ok(res)
+}
+
+/// how a part's timings have moved on its current input
+pub struct Trend {
+    pub best: Entry,
+    pub latest: Entry,
+    /// the medians, oldest first
+    pub medians: Vec<u64>,
+}
+
+impl Trend {
+    /// how much slower the latest run is than the best, in percent
+    pub fn change(&self) -> f64 {
+        let best = self.best.median_us.max(1) as f64;
+        (self.latest.median_us as f64 - best) / best * 100.0
+    }
+}
+
+/// a trend for each part in the history, only counting runs on the input
+/// the part was last run on, since another input isn't comparable
+pub fn trends(history: &[Entry]) -> Vec<Trend> {
+    let mut parts: BTreeMap<(u8, u8), Vec<&Entry>> = BTreeMap::new();
+    for entry in history {
+        parts
+            .entry((entry.day, entry.part))
+            .or_default()
+            .push(entry);
+    }
+    parts
+        .into_values()
+        .map(|entries| {
+            let latest = *entries.last().unwrap();
+            let runs: Vec<&Entry> = entries
+                .into_iter()
+                .filter(|e| e.input_hash == latest.input_hash)
+                .collect();
+            let best = runs.iter().min_by_key(|e| e.median_us).unwrap();
+            Trend {
+                best: (*best).clone(),
+                latest: latest.clone(),
+                medians: runs.iter().map(|e| e.median_us).collect(),
+            }
+        })
+        .collect()
+}
+
+/// how many of the latest medians to show
+const RECENT: usize = 5;
+
+pub fn table(trends: &[Trend], threshold: f64) -> String {
+    let mut rows = vec![vec![
+        "day".to_string(),
+        "part".to_string(),
+        "best".to_string(),
+        "at".to_string(),
+        "latest".to_string(),
+        "at".to_string(),
+        "change".to_string(),
+        "allocs".to_string(),
+        "recent".to_string(),
+    ]];
+    let time = |us: u64| format!("{:?}", Duration::from_micros(us));
+    for t in trends {
+        let recent: Vec<String> = t.medians
+            [t.medians.len().saturating_sub(RECENT)..]
+            .iter()
+            .map(|us| time(*us))
+            .collect();
+        rows.push(vec![
+            t.latest.day.to_string(),
+            t.latest.part.to_string(),
+            time(t.best.median_us),
+            t.best.commit.clone(),
+            time(t.latest.median_us),
+            t.latest.commit.clone(),
+            format!("{:+.0}%", t.change()),
+            t.latest.allocations.to_string(),
+            recent.join(" "),
+            if t.change() > threshold {
+                "! slower"
+            } else {
+                ""
+            }
+            .to_string(),
+        ]);
+    }
+    output::align(&rows)
+}
+
+/// prints the trends, and fails if any part got more than `threshold`
+/// percent slower than its best
+pub fn report(trends: &[Trend], threshold: f64) -> Result<(), Box<dyn Error>> {
+    if trends.is_empty() {
+        return Err("no benchmarks recorded yet, run bench first".into());
+    }
+    print!("{}", table(trends, threshold));
+    let slower = trends.iter().filter(|t| t.change() > threshold).count();
+    if slower > 0 {
+        return Err(format!("{} part(s) got slower", slower).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    fn entry(
+        commit: &str,
+        part: u8,
+        input_hash: &str,
+        median_us: u64,
+    ) -> Entry {
+        Entry {
+            commit: commit.to_string(),
+            timestamp: 0,
+            day: 7,
+            part,
+            input_hash: input_hash.to_string(),
+            median_us,
+            allocations: 12,
+        }
+    }
+
+    fn history() -> Vec<Entry> {
+        vec![
+            entry("aaa", 1, "x", 3000),
+            entry("aaa", 2, "x", 9000),
+            entry("bbb", 1, "x", 2000),
+            entry("bbb", 2, "x", 100),
+            entry("ccc", 1, "x", 2600),
+            entry("ccc", 2, "y", 8000),
+        ]
+    }
+
+    #[test]
+    fn test_trends() {
+        let trends = trends(&history());
+        assert_eq!(trends.len(), 2);
+        assert_eq!(trends[0].best.commit, "bbb");
+        assert_eq!(trends[0].latest.commit, "ccc");
+        assert_eq!(trends[0].medians, vec![3000, 2000, 2600]);
+        assert_eq!(trends[0].change(), 30.0);
+        // part 2's input changed, so its earlier runs don't count
+        assert_eq!(trends[1].medians, vec![8000]);
+        assert_eq!(trends[1].change(), 0.0);
+    }
+
+    #[test]
+    fn test_table() {
+        let trends = trends(&history());
+        assert_eq!(
+            table(&trends, 20.0),
+            "day  part  best  at   latest  at   change  allocs  recent\n\
+             7    1     2ms   bbb  2.6ms   ccc  +30%    12      3ms 2ms 2.6ms  ! slower\n\
+             7    2     8ms   ccc  8ms     ccc  +0%     12      8ms\n"
+        );
+        assert!(report(&trends, 50.0).is_ok());
+        assert!(report(&trends, 20.0).is_err());
+        assert!(report(&[], 20.0).is_err());
+    }
+
+    #[test]
+    fn test_append() {
+        let dir = TestDir::new("history");
+        let path = dir.join("history.jsonl");
+        assert_eq!(load(&path).unwrap(), vec![]);
+        append(&path, &history()[..2]).unwrap();
+        append(&path, &history()[2..]).unwrap();
+        assert_eq!(load(&path).unwrap(), history());
+
+        std::fs::write(&path, "{\"commit\":").unwrap();
+        assert!(load(&path).is_err());
+    }
+
+    #[test]
+    fn test_bench() {
+        let root = TestDir::new("bench");
+        std::fs::create_dir_all(root.join("day9")).unwrap();
+        std::fs::write(root.join("day9/input.txt"), "2333133121414131402")
+            .unwrap();
+        let entries = bench(&root, Some(9), 3);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].commit, "unknown");
+        assert_eq!(entries[1].part, 2);
+        assert_eq!(entries[1].input_hash, run::hash("2333133121414131402"));
+    }
+}
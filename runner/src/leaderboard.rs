@@ -0,0 +1,324 @@
+// a private leaderboard, from the json export on its page. everything is
+// worked out from the saved file, it's never fetched
+
+use std::{collections::HashMap, error::Error, path::Path};
+
+use serde::Deserialize;
+
+use crate::output;
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// members who haven't set a name are anonymous
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// day, then part, to when the star was got
+    #[serde(default)]
+    pub completion_day_level: HashMap<u8, HashMap<u8, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    fn star_ts(&self, day: u8, part: u8) -> Option<i64> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        Some(star.get_star_ts)
+    }
+}
+
+pub fn load(path: &Path) -> Result<Leaderboard, Box<dyn Error>> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// unix time a day's puzzle unlocked, midnight at utc-5
+fn unlock_ts(year: i64, day: u8) -> i64 {
+    // days since 1970-01-01 of december the first, from howard hinnant's
+    // days_from_civil
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * 9 + 2) / 5;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468 + day as i64 - 1;
+    days * 86400 + 5 * 3600
+}
+
+/// e.g. 1:02:03, hours going past 24 for late stars
+fn clock(secs: i64) -> String {
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+impl Leaderboard {
+    /// members by score, best first
+    fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|m| (std::cmp::Reverse(m.local_score), m.id));
+        members
+    }
+
+    /// the last day anyone got a star on
+    fn last_day(&self) -> u8 {
+        self.members
+            .values()
+            .flat_map(|m| m.completion_day_level.keys())
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// how long after unlocking the member got the star
+    fn solve_time(&self, member: &Member, day: u8, part: u8) -> Option<i64> {
+        let year = self.event.parse().ok()?;
+        Some(member.star_ts(day, part)? - unlock_ts(year, day))
+    }
+
+    /// each member's local score at the end of each day, scored the way
+    /// the site does: with n members, the first to a star gets n points,
+    /// the next n - 1, and so on
+    fn scores_by_day(&self) -> Vec<HashMap<u64, u64>> {
+        let n = self.members.len() as u64;
+        let mut totals: HashMap<u64, u64> =
+            self.members.values().map(|m| (m.id, 0)).collect();
+        let mut res = Vec::new();
+        for day in 1..=self.last_day() {
+            for part in 1..=2 {
+                let mut stars: Vec<(i64, u64)> = self
+                    .members
+                    .values()
+                    .filter_map(|m| Some((m.star_ts(day, part)?, m.id)))
+                    .collect();
+                stars.sort();
+                for (i, (_, id)) in stars.iter().enumerate() {
+                    *totals.get_mut(id).unwrap() += n - i as u64;
+                }
+            }
+            res.push(totals.clone());
+        }
+        res
+    }
+
+    pub fn standings(&self) -> String {
+        let mut rows = vec![vec![
+            "rank".to_string(),
+            "member".to_string(),
+            "stars".to_string(),
+            "score".to_string(),
+        ]];
+        for (i, m) in self.ranked().iter().enumerate() {
+            rows.push(vec![
+                (i + 1).to_string(),
+                m.display_name(),
+                m.stars.to_string(),
+                m.local_score.to_string(),
+            ]);
+        }
+        output::align(&rows)
+    }
+
+    /// how long each member took on each part of the day, and between the
+    /// two parts, quickest first
+    pub fn times(&self, day: u8) -> String {
+        let mut rows = vec![vec![
+            "day".to_string(),
+            "member".to_string(),
+            "part 1".to_string(),
+            "part 2".to_string(),
+            "delta".to_string(),
+        ]];
+        let mut members: Vec<(i64, i64, &Member)> = self
+            .members
+            .values()
+            .filter_map(|m| {
+                let p1 = self.solve_time(m, day, 1)?;
+                let p2 = self.solve_time(m, day, 2).unwrap_or(i64::MAX);
+                Some((p1, p2, m))
+            })
+            .collect();
+        members.sort_by_key(|(p1, p2, m)| (*p2, *p1, m.id));
+        for (p1, p2, m) in members {
+            let (p2, delta) = match p2 {
+                i64::MAX => (String::new(), String::new()),
+                p2 => (clock(p2), clock(p2 - p1)),
+            };
+            rows.push(vec![
+                day.to_string(),
+                m.display_name(),
+                clock(p1),
+                p2,
+                delta,
+            ]);
+        }
+        output::align(&rows)
+    }
+
+    /// where each member ranked at the end of each day, and how far they
+    /// moved since the day before
+    pub fn ranks(&self) -> String {
+        let by_day = self.scores_by_day();
+        let mut rows = vec![std::iter::once("member".to_string())
+            .chain((1..=by_day.len()).map(|d| format!("day {}", d)))
+            .collect::<Vec<String>>()];
+        for m in self.ranked() {
+            let mut row = vec![m.display_name()];
+            let mut before = None;
+            for scores in &by_day {
+                let rank = 1 + scores
+                    .iter()
+                    .filter(|(id, score)| {
+                        **score > scores[&m.id]
+                            || (**score == scores[&m.id] && **id < m.id)
+                    })
+                    .count();
+                row.push(match before {
+                    Some(b) if b > rank => format!("{} +{}", rank, b - rank),
+                    Some(b) if b < rank => format!("{} -{}", rank, rank - b),
+                    _ => rank.to_string(),
+                });
+                before = Some(rank);
+            }
+            rows.push(row);
+        }
+        output::align(&rows)
+    }
+
+    /// the standings, then every day's times (or just `day`'s), then
+    /// how the ranks changed
+    pub fn report(&self, day: Option<u8>) -> String {
+        let days = match day {
+            Some(day) => day..=day,
+            None => 1..=self.last_day(),
+        };
+        let mut sections = vec![self.standings()];
+        sections.extend(days.map(|d| self.times(d)));
+        sections.push(self.ranks());
+        sections.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // day 1 unlocked at 1733029200, day 2 at 1733115600
+    const EXPORT: &str = r#"{
+        "event": "2024",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 3, "local_score": 8,
+                "global_score": 0, "last_star_ts": 1733116000,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1733029500, "star_index": 1},
+                        "2": {"get_star_ts": 1733030100, "star_index": 2}
+                    },
+                    "2": {
+                        "1": {"get_star_ts": 1733116000, "star_index": 5}
+                    }
+                }
+            },
+            "2": {
+                "id": 2, "name": "bob", "stars": 4, "local_score": 10,
+                "global_score": 0, "last_star_ts": 1733116200,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1733029400, "star_index": 3},
+                        "2": {"get_star_ts": 1733036400, "star_index": 4}
+                    },
+                    "2": {
+                        "1": {"get_star_ts": 1733116100, "star_index": 6},
+                        "2": {"get_star_ts": 1733116200, "star_index": 7}
+                    }
+                }
+            },
+            "3": {
+                "id": 3, "name": null, "stars": 0, "local_score": 0,
+                "global_score": 0, "last_star_ts": 0,
+                "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    fn leaderboard() -> Leaderboard {
+        serde_json::from_str(EXPORT).unwrap()
+    }
+
+    #[test]
+    fn test_unlock_ts() {
+        assert_eq!(unlock_ts(2024, 1), 1733029200);
+        assert_eq!(unlock_ts(2024, 25), 1735102800);
+        assert_eq!(unlock_ts(2015, 1), 1448946000);
+    }
+
+    #[test]
+    fn test_scores_by_day() {
+        let lb = leaderboard();
+        let by_day = lb.scores_by_day();
+        assert_eq!(by_day.len(), 2);
+        assert_eq!(by_day[0][&1], 5);
+        assert_eq!(by_day[0][&2], 5);
+        // ends up where the site put them
+        for m in lb.members.values() {
+            assert_eq!(by_day[1][&m.id], m.local_score);
+        }
+    }
+
+    #[test]
+    fn test_standings() {
+        assert_eq!(
+            leaderboard().standings(),
+            "rank  member               stars  score\n\
+             1     bob                  4      10\n\
+             2     alice                3      8\n\
+             3     (anonymous user #3)  0      0\n"
+        );
+    }
+
+    #[test]
+    fn test_times() {
+        let lb = leaderboard();
+        assert_eq!(
+            lb.times(1),
+            "day  member  part 1   part 2   delta\n\
+             1    alice   0:05:00  0:15:00  0:10:00\n\
+             1    bob     0:03:20  2:00:00  1:56:40\n"
+        );
+        assert_eq!(
+            lb.times(2),
+            "day  member  part 1   part 2   delta\n\
+             2    bob     0:08:20  0:10:00  0:01:40\n\
+             2    alice   0:06:40\n"
+        );
+        assert_eq!(lb.times(3), "day  member  part 1  part 2  delta\n");
+    }
+
+    #[test]
+    fn test_ranks() {
+        assert_eq!(
+            leaderboard().ranks(),
+            "member               day 1  day 2\n\
+             bob                  2      1 +1\n\
+             alice                1      2 -1\n\
+             (anonymous user #3)  3      3\n"
+        );
+    }
+}
@@ -0,0 +1,21 @@
+pub mod allocs;
+pub mod batch;
+pub mod budget;
+pub mod cache;
+pub mod days;
+pub mod describe;
+pub mod detect;
+pub mod diff;
+pub mod external;
+pub mod history;
+pub mod leaderboard;
+pub mod minimize;
+pub mod output;
+pub mod panics;
+pub mod run;
+pub mod serve;
+pub mod status;
+pub mod store;
+#[cfg(test)]
+mod test_dir;
+pub mod watch;
@@ -0,0 +1,427 @@
+use std::{
+    io::{stderr, stdout, IsTerminal},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use clap::{Parser, Subcommand};
+
+use runner::{
+    allocs, batch, budget,
+    cache::{self, Cache},
+    days, describe, detect, diff, external, history, leaderboard,
+    minimize::{self, Predicate},
+    output::{self, Format},
+    run, serve, status,
+    store::{self, Keys},
+    watch,
+};
+
+#[global_allocator]
+static ALLOCATOR: allocs::Counting = allocs::Counting;
+
+#[derive(Parser)]
+#[command(about = "runs the advent of code solutions")]
+struct Cli {
+    /// directory containing the dayN folders, defaults to the workspace root
+    #[arg(long, global = true)]
+    root: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// solve each part against its day's input.txt
+    Run {
+        #[arg(short, long)]
+        day: Option<u8>,
+        #[arg(short, long)]
+        part: Option<u8>,
+        /// use this file instead of the day's input.txt
+        #[arg(short, long, requires = "day")]
+        input: Option<PathBuf>,
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// also show how each answer was reached, where the day supports it
+        #[arg(short, long)]
+        explain: bool,
+        /// refuse inputs with crlf line endings, trailing whitespace etc.
+        /// instead of cleaning them up
+        #[arg(long)]
+        strict: bool,
+        /// solve every part again, even if its answer is cached
+        #[arg(long)]
+        no_cache: bool,
+        /// give up on any part still going after this many seconds. parts
+        /// that report their progress are stopped, any other is left
+        /// running in the background until the runner exits
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+    /// solve each part against every input in `dir/dayN/*.txt`, flagging
+    /// the ones it fails on or is much slower on
+    Batch {
+        dir: PathBuf,
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// give up on any part still going after this many seconds. parts
+        /// that report their progress are stopped, any other is left
+        /// running in the background until the runner exits
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+    /// solve each part against its day's input.txt, failing if any takes
+    /// longer than its budget, worst offenders first
+    Budget {
+        #[arg(short, long)]
+        day: Option<u8>,
+    },
+    /// time each part against its day's input.txt, and add the results
+    /// to the benchmark history
+    Bench {
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// how many times to solve each part, the median is kept
+        #[arg(long, default_value_t = 10)]
+        samples: usize,
+        /// defaults to bench-history.jsonl in the root
+        #[arg(long)]
+        history: Option<PathBuf>,
+    },
+    /// show how each part's time has changed over the benchmark history,
+    /// failing if any got slower than its best by more than `threshold`
+    Trend {
+        /// in percent
+        #[arg(long, default_value_t = 20.0)]
+        threshold: f64,
+        #[arg(long)]
+        history: Option<PathBuf>,
+    },
+    /// statistics about a day's input, without solving it
+    Describe {
+        day: u8,
+        /// use this file instead of the day's input.txt
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// guess which day an input is for
+    Detect { input: PathBuf },
+    /// check that every variant of a part agrees with its solver, on the
+    /// day's fixtures and on generated inputs
+    Diff {
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// how many inputs to generate for each day
+        #[arg(long, default_value_t = 100)]
+        cases: usize,
+        #[arg(long, default_value_t = 2024)]
+        seed: u64,
+    },
+    /// shrink an input a part fails on, and save it as a new fixture. by
+    /// default it fails by panicking
+    Minimize {
+        day: u8,
+        part: u8,
+        input: PathBuf,
+        /// fail by disagreeing with this variant of the part
+        #[arg(long, conflicts_with = "expected")]
+        variant: Option<String>,
+        /// fail by answering anything other than this
+        #[arg(long)]
+        expected: Option<String>,
+        /// give up on any shrunk input the part takes longer than this many
+        /// seconds on, counting it as not failing
+        #[arg(long, default_value_t = 10)]
+        timeout: u64,
+    },
+    /// compare another solver with ours, on the day's fixtures and input.
+    /// it's given each input on stdin and should print one answer per line
+    Compare {
+        day: u8,
+        /// give up on either solver after this many seconds. only our
+        /// parts that report their progress can be stopped
+        #[arg(long, default_value_t = 30)]
+        timeout: u64,
+        /// the command to run, e.g. `-- python3 ../py/day7.py`
+        #[arg(required = true, trailing_var_arg = true)]
+        command: Vec<String>,
+    },
+    /// encrypt every day's input.txt and answers.txt, so only the locked
+    /// copies are left. the passphrase is read from $AOC_PASSPHRASE
+    Lock,
+    /// decrypt the locked inputs and answers back into plaintext. files
+    /// edited since they were locked are kept unless forced
+    Unlock {
+        /// overwrite plaintext that differs from its locked copy
+        #[arg(long)]
+        force: bool,
+    },
+    /// stars, solve times and rank changes from a private leaderboard's
+    /// json export
+    Leaderboard {
+        file: PathBuf,
+        /// only show the times for this day
+        #[arg(short, long)]
+        day: Option<u8>,
+    },
+    /// the calendar: which parts are solved, whether their answers match
+    /// the known ones, and how long they took last time
+    Status,
+    /// re-run a day whenever its code, input or fixtures change
+    Watch { day: u8 },
+    /// serve the solutions over http, e.g. POST /2024/day/7/part/1
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:8024")]
+        addr: String,
+        /// give up on any part still going after this many seconds. only
+        /// parts that report their progress can be stopped, the others are
+        /// always run to the end
+        #[arg(long, default_value_t = 30)]
+        timeout: u64,
+    },
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let root = cli.root.unwrap_or_else(run::default_root);
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input: input_file,
+            format,
+            explain,
+            strict,
+            no_cache,
+            timeout,
+        } => {
+            let timeout = timeout.map(Duration::from_secs);
+            if timeout.is_some() {
+                warn_uncancellable(day, part);
+            }
+            let mut cache = (!no_cache).then(|| {
+                Cache::open(&cache::default_path(), &run::default_root())
+            });
+            let mut runs = Vec::new();
+            for solver in days::select(day, part) {
+                let loaded = match &input_file {
+                    Some(path) => run::load_file(path, strict),
+                    None => run::load_input(&root, solver.day, strict),
+                };
+                let input = match loaded {
+                    Ok(input) => input,
+                    Err(e) => {
+                        eprintln!("skipping day {}: {}", solver.day, e);
+                        continue;
+                    }
+                };
+                let solve = || {
+                    let mut progress = show_progress(solver.day, solver.part);
+                    let run = run::run_limited(
+                        solver,
+                        &input,
+                        explain,
+                        timeout,
+                        &mut progress,
+                    );
+                    if stderr().is_terminal() {
+                        eprint!("\r\x1b[K");
+                    }
+                    run
+                };
+                runs.push(match &mut cache {
+                    Some(cache) => {
+                        cache::run(cache, solver, &input, explain, solve)
+                    }
+                    None => solve(),
+                });
+            }
+            if let Some(cache) = &cache {
+                if let Err(e) = cache.save() {
+                    eprintln!("couldn't save the cache: {}", e);
+                }
+            }
+            output::write(&runs, format, stdout())?;
+            let failed = runs.iter().filter(|r| r.failure.is_some()).count();
+            if failed > 0 {
+                return Err(format!("{} part(s) failed", failed).into());
+            }
+        }
+        Command::Batch { dir, day, timeout } => {
+            let timeout = timeout.map(Duration::from_secs);
+            if timeout.is_some() {
+                warn_uncancellable(day, None);
+            }
+            print!("{}", batch::table(&batch::batch(&dir, day, timeout)));
+        }
+        Command::Bench {
+            day,
+            samples,
+            history: path,
+        } => {
+            let path = path.unwrap_or_else(|| history::default_path(&root));
+            let entries = history::bench(&root, day, samples);
+            history::append(&path, &entries)?;
+            let recorded = history::load(&path)?;
+            let trends = history::trends(&recorded);
+            print!("{}", history::table(&trends, f64::INFINITY));
+        }
+        Command::Trend {
+            threshold,
+            history: path,
+        } => {
+            let path = path.unwrap_or_else(|| history::default_path(&root));
+            history::report(
+                &history::trends(&history::load(&path)?),
+                threshold,
+            )?
+        }
+        Command::Budget { day } => budget::report(&budget::check(&root, day))?,
+        Command::Describe { day, input } => {
+            let input = match input {
+                Some(path) => run::load_file(&path, false)?,
+                None => run::load_input(&root, day, false)?,
+            };
+            print!("{}", describe::format(&describe::describe(day, &input)));
+        }
+        Command::Detect { input } => {
+            let input = run::load_file(&input, false)?;
+            print!("{}", detect::report(&detect::detect(&input)));
+        }
+        Command::Diff { day, cases, seed } => {
+            diff::diff(&root, day, cases, seed)?
+        }
+        Command::Minimize {
+            day,
+            part,
+            input,
+            variant,
+            expected,
+            timeout,
+        } => {
+            let solver = days::select(Some(day), Some(part))
+                .into_iter()
+                .next()
+                .ok_or(format!("day {} part {} isn't solved", day, part))?;
+            let predicate = match (variant, expected) {
+                (Some(name), _) => Predicate::DiffersFrom(
+                    days::variants(day, part)
+                        .into_iter()
+                        .find(|v| v.name == name)
+                        .ok_or(format!("no variant called {}", name))?,
+                ),
+                (_, Some(expected)) => Predicate::DiffersFromExpected(expected),
+                (None, None) => Predicate::Panics,
+            };
+            let input = run::load_file(&input, false)?;
+            let minimized = minimize::minimize_for(
+                solver,
+                &predicate,
+                &input,
+                Duration::from_secs(timeout),
+            )?;
+            let path = minimize::write_fixture(&root, day, &minimized)?;
+            println!("wrote {}:", path.display());
+            println!("{}", minimized);
+        }
+        Command::Compare {
+            day,
+            timeout,
+            command,
+        } => {
+            let timeout = Duration::from_secs(timeout);
+            external::report(&external::compare(
+                &root, day, &command, timeout,
+            )?)?
+        }
+        Command::Leaderboard { file, day } => {
+            print!("{}", leaderboard::load(&file)?.report(day))
+        }
+        Command::Lock => {
+            let passphrase = if store::has_locked(&root) {
+                store::passphrase()?
+            } else {
+                store::new_passphrase()?
+            };
+            let mut keys = Keys::new(&passphrase);
+            for path in store::lock(&root, &mut keys)? {
+                println!("locked {}", path.display());
+            }
+        }
+        Command::Unlock { force } => {
+            let mut keys = Keys::new(&store::passphrase()?);
+            let res = store::unlock(&root, &mut keys, force)?;
+            for path in res.unlocked {
+                println!("unlocked {}", path.display());
+            }
+            for path in &res.kept {
+                eprintln!(
+                    "kept {}, it's been edited since it was locked",
+                    path.display()
+                );
+            }
+            if !res.kept.is_empty() {
+                return Err(format!(
+                    "{} file(s) kept, lock them or pass --force to overwrite",
+                    res.kept.len()
+                )
+                .into());
+            }
+        }
+        Command::Status => {
+            let mut cache =
+                Cache::open(&cache::default_path(), &run::default_root());
+            let history = history::load(&history::default_path(&root))?;
+            let days = status::status(&root, &mut cache, &history);
+            print!("{}", status::table(&days));
+        }
+        Command::Watch { day } => watch::watch(&root, day)?,
+        Command::Serve { addr, timeout } => {
+            serve::serve(&addr, Duration::from_secs(timeout))?
+        }
+    }
+
+    Ok(())
+}
+
+/// says which of the parts `--timeout` can stop, the others are only given
+/// up on and keep running in the background
+fn warn_uncancellable(day: Option<u8>, part: Option<u8>) {
+    let (cancellable, uncancellable): (Vec<&days::Solver>, Vec<_>) =
+        days::select(day, part)
+            .into_iter()
+            .partition(|s| run::cancellable(s));
+    if uncancellable.is_empty() {
+        return;
+    }
+    let names: Vec<String> = cancellable
+        .iter()
+        .map(|s| format!("day {} part {}", s.day, s.part))
+        .collect();
+    eprintln!(
+        "--timeout only stops parts that report their progress, {} of the \
+         {} here: {}. the rest are left running once they time out",
+        names.len(),
+        uncancellable.len() + cancellable.len(),
+        if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        }
+    );
+}
+
+/// prints how far a slow part has got on stderr, when someone's watching
+fn show_progress(day: u8, part: u8) -> impl FnMut(usize, usize) {
+    let watched = stderr().is_terminal();
+    let mut last = Instant::now();
+    move |done, total| {
+        if watched && last.elapsed() > Duration::from_millis(100) {
+            last = Instant::now();
+            eprint!("\rday {} part {}: {} of {}", day, part, done, total);
+        }
+    }
+}
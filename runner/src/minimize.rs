@@ -0,0 +1,317 @@
+// delta debugging: shrinks an input that makes a solver misbehave, by
+// removing lines, grid columns and tokens for as long as it still does
+
+use std::{
+    error::Error,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    days::{Solver, Variant},
+    diff::Outcome,
+    run,
+};
+
+/// what counts as the input still failing
+pub enum Predicate<'a> {
+    /// the solver panics with the same message, from the same place, as
+    /// it did on the original input
+    Panics,
+    /// the solver and a variant of it disagree, in the same way as they
+    /// did on the original input
+    DiffersFrom(&'a Variant),
+    /// the solver answers, but not with this. mostly useful for answers
+    /// that shouldn't depend on the input much, e.g. that should be 0
+    DiffersFromExpected(String),
+}
+
+/// shrinks `input` while `predicate` keeps holding for it. shrinking can
+/// leave an input the solver never finishes, e.g. a grid the guard walks
+/// round forever, so each try is given up on after `timeout`, and one
+/// that's given up on doesn't count as failing
+pub fn minimize_for(
+    solver: &Solver,
+    predicate: &Predicate,
+    input: &str,
+    timeout: Duration,
+) -> Result<String, Box<dyn Error>> {
+    if attempt(solver, input, timeout).is_none() {
+        return Err(format!(
+            "the solver takes longer than {:?} on that input",
+            timeout
+        )
+        .into());
+    }
+    match predicate {
+        Predicate::Panics => {
+            let panic = |i: &str| {
+                attempt(solver, i, timeout)?
+                    .err()
+                    .filter(|f| f.starts_with("panicked"))
+            };
+            let Some(original) = panic(input) else {
+                return Err("the solver doesn't panic on that input".into());
+            };
+            Ok(minimize(input, |i| panic(i).as_ref() == Some(&original)))
+        }
+        Predicate::DiffersFrom(variant) => {
+            let other = Solver {
+                solve: variant.solve,
+                explain: None,
+                progress: None,
+                ..*solver
+            };
+            let outcomes = |i: &str| {
+                Some((
+                    attempt(solver, i, timeout)?,
+                    attempt(&other, i, timeout)?,
+                ))
+            };
+            let Some(original) = outcomes(input).filter(|(a, b)| a != b) else {
+                return Err(format!(
+                    "the solver and {} agree on that input",
+                    variant.name
+                )
+                .into());
+            };
+            Ok(minimize(input, |i| outcomes(i).as_ref() == Some(&original)))
+        }
+        Predicate::DiffersFromExpected(expected) => {
+            let differs = |i: &str| {
+                attempt(solver, i, timeout)
+                    .is_some_and(|o| o.is_ok_and(|answer| answer != *expected))
+            };
+            if !differs(input) {
+                return Err(format!(
+                    "the solver doesn't answer anything but {}",
+                    expected
+                )
+                .into());
+            }
+            Ok(minimize(input, differs))
+        }
+    }
+}
+
+/// what the solver makes of an input, or `None` if it took too long
+fn attempt(solver: &Solver, input: &str, timeout: Duration) -> Option<Outcome> {
+    let run =
+        run::run_limited(solver, input, false, Some(timeout), &mut |_, _| {});
+    match run.failure {
+        None => Some(Ok(run.answer)),
+        Some(failure) if failure.starts_with("timed out") => None,
+        Some(failure) => Some(Err(failure)),
+    }
+}
+
+/// the smallest input found for which `fails` still holds, trying lines,
+/// then grid columns, then tokens, until none of them shrink it further
+pub fn minimize(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut current = input.to_string();
+    loop {
+        let before = current.clone();
+
+        let lines: Vec<&str> = current.lines().collect();
+        current = ddmin(lines, |ls| fails(&ls.join("\n"))).join("\n");
+
+        let rows: Vec<Vec<char>> =
+            current.lines().map(|l| l.chars().collect()).collect();
+        if is_grid(&rows) {
+            let columns = (0..rows[0].len()).collect();
+            let keep = ddmin(columns, |cs| fails(&select_columns(&rows, cs)));
+            current = select_columns(&rows, &keep);
+        }
+
+        let tokens: Vec<(usize, &str)> = current
+            .lines()
+            .enumerate()
+            .flat_map(|(i, l)| l.split(' ').map(move |t| (i, t)))
+            .collect();
+        let line_count = current.lines().count();
+        let keep = ddmin(tokens, |ts| fails(&join_tokens(ts, line_count)));
+        current = join_tokens(&keep, line_count);
+
+        if current == before {
+            return current;
+        }
+    }
+}
+
+/// tries removing ever smaller chunks of `items`, keeping every removal
+/// after which `fails` still holds
+fn ddmin<T: Clone>(mut items: Vec<T>, fails: impl Fn(&[T]) -> bool) -> Vec<T> {
+    let mut chunks = 2;
+    while !items.is_empty() {
+        let size = items.len().div_ceil(chunks);
+        let smaller = (0..items.len()).step_by(size).find_map(|start| {
+            let end = (start + size).min(items.len());
+            let candidate: Vec<T> = [&items[..start], &items[end..]].concat();
+            fails(&candidate).then_some(candidate)
+        });
+        match smaller {
+            Some(smaller) => {
+                items = smaller;
+                chunks = (chunks - 1).max(2);
+            }
+            None if size == 1 => break,
+            None => chunks = (chunks * 2).min(items.len()),
+        }
+    }
+    items
+}
+
+/// at least two rows, all the same width
+fn is_grid(rows: &[Vec<char>]) -> bool {
+    rows.len() > 1
+        && rows[0].len() > 1
+        && rows.iter().all(|r| r.len() == rows[0].len())
+}
+
+fn select_columns(rows: &[Vec<char>], columns: &[usize]) -> String {
+    rows.iter()
+        .map(|r| columns.iter().map(|c| r[*c]).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn join_tokens(tokens: &[(usize, &str)], line_count: usize) -> String {
+    (0..line_count)
+        .map(|i| {
+            tokens
+                .iter()
+                .filter(|(line, _)| *line == i)
+                .map(|(_, t)| *t)
+                .collect::<Vec<&str>>()
+                .join(" ")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// saves the input next to the day's other fixtures, under the first
+/// free name of test_input_min.txt, test_input_min2.txt, ... an empty
+/// input isn't worth keeping as a fixture, so it's refused
+pub fn write_fixture(root: &Path, day: u8, input: &str) -> io::Result<PathBuf> {
+    if input.trim().is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the input shrank to nothing, so there's no fixture to write",
+        ));
+    }
+    let dir = root.join(format!("day{}", day));
+    let path = (1..)
+        .map(|n| match n {
+            1 => dir.join("test_input_min.txt"),
+            n => dir.join(format!("test_input_min{}.txt", n)),
+        })
+        .find(|path| !path.exists())
+        .unwrap();
+    std::fs::write(&path, format!("{}\n", input))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days, test_dir::TestDir};
+
+    #[test]
+    fn test_ddmin() {
+        let items: Vec<u32> = (1..=20).collect();
+        let res = ddmin(items, |xs| xs.contains(&3) && xs.contains(&17));
+        assert_eq!(res, vec![3, 17]);
+        let res = ddmin(vec![1, 2, 3], |_| true);
+        assert_eq!(res, Vec::<u32>::new());
+        let res = ddmin(vec![1, 2, 3], |xs| xs.len() == 3);
+        assert_eq!(res, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_minimize() {
+        let input = "ab\ncd\nef gh\nij";
+        assert_eq!(minimize(input, |i| i.contains("gh")), "gh");
+        assert_eq!(minimize(input, |i| i.starts_with("cd")), "cd");
+        assert_eq!(minimize(input, |_| false), input);
+
+        let grid = "....\n.#..\n...#\n....";
+        let two_walls = |i: &str| i.matches('#').count() == 2;
+        assert_eq!(minimize(grid, two_walls), "#.\n.#");
+    }
+
+    // counts walls, but can't stand 7s, or nothing at all
+    const SOLVER: Solver = Solver {
+        day: 1,
+        part: 1,
+        solve: |input| {
+            if input.is_empty() {
+                panic!("nothing to count");
+            }
+            if input.contains('7') {
+                panic!("can't handle 7s");
+            }
+            input.matches('#').count().into()
+        },
+        explain: None,
+        progress: None,
+        budget: days::BUDGET,
+    };
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn test_minimize_for() {
+        let panics = Predicate::Panics;
+        let res =
+            minimize_for(&SOLVER, &panics, "1 2 3\n4 5 6\n7 8 9", TIMEOUT);
+        // the empty input panics too, but not in the same way
+        assert_eq!(res.unwrap(), "7");
+        assert!(minimize_for(&SOLVER, &panics, "1 2 3", TIMEOUT).is_err());
+        let res = minimize_for(&SOLVER, &panics, "", TIMEOUT);
+        assert_eq!(res.unwrap(), "");
+
+        let expected = Predicate::DiffersFromExpected("0".to_string());
+        assert!(minimize_for(&SOLVER, &expected, "...\n...", TIMEOUT).is_err());
+        let res = minimize_for(&SOLVER, &expected, "..#\n#..\n...", TIMEOUT);
+        assert_eq!(res.unwrap(), "#..");
+
+        let variant = days::variants(7, 2)[0];
+        let differs = Predicate::DiffersFrom(variant);
+        let solver = days::select(Some(7), Some(2))[0];
+        assert!(minimize_for(solver, &differs, "156: 15 6", TIMEOUT).is_err());
+    }
+
+    #[test]
+    fn test_minimize_for_timeout() {
+        // hangs on any input without a wall, and panics on a 7
+        let solver = Solver {
+            solve: |input| {
+                if !input.contains('#') {
+                    std::thread::sleep(Duration::from_secs(60));
+                }
+                (SOLVER.solve)(input)
+            },
+            ..SOLVER
+        };
+        let timeout = Duration::from_millis(50);
+        let panics = Predicate::Panics;
+        let res = minimize_for(&solver, &panics, "#\n7 8", timeout);
+        assert_eq!(res.unwrap(), "#\n7");
+        let res = minimize_for(&solver, &panics, "7 8", timeout);
+        assert!(res.unwrap_err().to_string().starts_with("the solver takes"));
+    }
+
+    #[test]
+    fn test_write_fixture() {
+        let root = TestDir::new("write_fixture");
+        std::fs::create_dir_all(root.join("day3")).unwrap();
+        let first = write_fixture(&root, 3, "mul(2,4)").unwrap();
+        let second = write_fixture(&root, 3, "mul(3,5)").unwrap();
+        assert_eq!(first, root.join("day3/test_input_min.txt"));
+        assert_eq!(second, root.join("day3/test_input_min2.txt"));
+        assert_eq!(std::fs::read_to_string(second).unwrap(), "mul(3,5)\n");
+        assert!(write_fixture(&root, 3, "\n").is_err());
+        assert!(!root.join("day3/test_input_min3.txt").exists());
+    }
+}
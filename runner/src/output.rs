@@ -0,0 +1,208 @@
+use std::{io::Write, time::Duration};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::run::Run;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Tsv,
+}
+
+pub fn write(
+    runs: &[Run],
+    format: Format,
+    mut w: impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        Format::Text => {
+            for r in runs {
+                if let Some(failure) = &r.failure {
+                    writeln!(w, "Day {} part {}: {}", r.day, r.part, failure)?;
+                    continue;
+                }
+                writeln!(
+                    w,
+                    "Day {} part {}: {} in {:?}{}",
+                    r.day,
+                    r.part,
+                    r.answer,
+                    Duration::from_micros(r.time_us),
+                    if r.cached { " (cached)" } else { "" }
+                )?;
+                for line in &r.explanation {
+                    writeln!(w, "    {}", line)?;
+                }
+            }
+        }
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut w, runs)?;
+            writeln!(w)?;
+        }
+        Format::Csv => write_delimited(runs, b',', w)?,
+        Format::Tsv => write_delimited(runs, b'\t', w)?,
+    }
+    Ok(())
+}
+
+// explanations don't fit in a table, so they're left out of csv and tsv
+#[derive(Serialize)]
+struct Row<'a> {
+    day: u8,
+    part: u8,
+    answer: &'a str,
+    time_us: u64,
+    input_hash: &'a str,
+    failure: Option<&'a str>,
+}
+
+impl<'a> From<&'a Run> for Row<'a> {
+    fn from(r: &'a Run) -> Self {
+        Row {
+            day: r.day,
+            part: r.part,
+            answer: &r.answer,
+            time_us: r.time_us,
+            input_hash: &r.input_hash,
+            failure: r.failure.as_deref(),
+        }
+    }
+}
+
+/// lines up the cells of each row into columns, for tables people read
+pub fn align(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|r| r.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    rows.iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect();
+            cells.join("  ").trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+fn write_delimited(
+    runs: &[Run],
+    delimiter: u8,
+    w: impl Write,
+) -> Result<(), csv::Error> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(w);
+    for r in runs {
+        writer.serialize(Row::from(r))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs() -> Vec<Run> {
+        vec![
+            Run {
+                day: 9,
+                part: 1,
+                answer: "1928".to_string(),
+                time_us: 12,
+                input_hash: "abc".to_string(),
+                explanation: vec![],
+                cached: true,
+                failure: None,
+            },
+            Run {
+                day: 9,
+                part: 2,
+                answer: "2858".to_string(),
+                time_us: 3400,
+                input_hash: "abc".to_string(),
+                explanation: vec!["moved 9 first".to_string()],
+                cached: false,
+                failure: None,
+            },
+            Run {
+                day: 14,
+                part: 2,
+                answer: String::new(),
+                time_us: 10_000_000,
+                input_hash: "def".to_string(),
+                explanation: vec![],
+                cached: false,
+                failure: Some("timed out after 40 of 10403".to_string()),
+            },
+        ]
+    }
+
+    fn to_string(format: Format) -> String {
+        let mut out = Vec::new();
+        write(&runs(), format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            to_string(Format::Text),
+            "Day 9 part 1: 1928 in 12µs (cached)\nDay 9 part 2: 2858 in 3.4ms\n    moved 9 first\nDay 14 part 2: timed out after 40 of 10403\n"
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            to_string(Format::Csv),
+            "day,part,answer,time_us,input_hash,failure\n9,1,1928,12,abc,\n9,2,2858,3400,abc,\n14,2,,10000000,def,timed out after 40 of 10403\n"
+        );
+    }
+
+    #[test]
+    fn test_tsv() {
+        assert_eq!(
+            to_string(Format::Tsv),
+            "day\tpart\tanswer\ttime_us\tinput_hash\tfailure\n9\t1\t1928\t12\tabc\t\n9\t2\t2858\t3400\tabc\t\n14\t2\t\t10000000\tdef\ttimed out after 40 of 10403\n"
+        );
+    }
+
+    #[test]
+    fn test_align() {
+        let rows = vec![
+            vec!["a".to_string(), "bb".to_string(), "c".to_string()],
+            vec!["ddd".to_string(), "e".to_string()],
+        ];
+        assert_eq!(align(&rows), "a    bb  c\nddd  e\n");
+        assert_eq!(align(&[]), "");
+    }
+
+    #[test]
+    fn test_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&to_string(Format::Json)).unwrap();
+        assert_eq!(json[0]["day"], 9);
+        assert_eq!(json[1]["answer"], "2858");
+        assert_eq!(json[1]["time_us"], 3400);
+        assert!(json[0].get("explanation").is_none());
+        assert_eq!(json[0]["cached"], true);
+        assert!(json[1].get("cached").is_none());
+        assert!(json[1].get("failure").is_none());
+        assert_eq!(json[2]["failure"], "timed out after 40 of 10403");
+        assert_eq!(json[1]["explanation"][0], "moved 9 first");
+    }
+}
@@ -0,0 +1,109 @@
+// a panic is how a part normally fails, from an unwrap in a parser or a
+// "no solution" it didn't expect. these are caught along with where they
+// came from, so they can be reported like any other failure instead of
+// taking the rest of the run down with them
+
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+/// a caught panic
+#[derive(Debug, Clone, PartialEq)]
+pub struct Panic {
+    pub message: String,
+    /// file:line:column
+    pub location: Option<String>,
+}
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => {
+                write!(f, "panicked at {}: {}", location, self.message)
+            }
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// wraps the panic hook, once, so that panics on a thread that's catching
+/// them have their location kept instead of being printed
+fn install_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                LOCATION.set(info.location().map(ToString::to_string));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// runs `f`, catching it if it panics
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, Panic> {
+    install_hook();
+    let catching = CATCHING.replace(true);
+    let res = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(catching);
+    res.map_err(|payload| Panic {
+        message: message(payload.as_ref()),
+        location: LOCATION.take(),
+    })
+}
+
+fn message(payload: &(dyn Any + Send)) -> String {
+    match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(s), _) => s.to_string(),
+        (_, Some(s)) => s.clone(),
+        _ => "unknown panic".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch() {
+        assert_eq!(catch(|| 4), Ok(4));
+
+        let panic =
+            catch(|| -> u32 { panic!("no solution for {}", 7) }).unwrap_err();
+        assert_eq!(panic.message, "no solution for 7");
+        let location = panic.location.clone().unwrap();
+        assert!(
+            location.starts_with("runner/src/panics.rs:"),
+            "{}",
+            location
+        );
+        assert_eq!(
+            panic.to_string(),
+            format!("panicked at {}: no solution for 7", location)
+        );
+
+        let panic = catch(|| "x".parse::<u32>().unwrap()).unwrap_err();
+        assert!(panic.message.contains("InvalidDigit"), "{}", panic.message);
+
+        // nested catches don't stop the outer one catching
+        let res = catch(|| {
+            assert!(catch(|| panic!("inner")).is_err());
+            panic!("outer")
+        });
+        assert_eq!(res.map(|_: ()| ()).unwrap_err().message, "outer");
+    }
+}
@@ -0,0 +1,360 @@
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utils::{
+    input::{normalize, normalize_strict, Irregularity},
+    progress::Cancelled,
+};
+
+use crate::{days::Solver, panics, store};
+
+/// the result of running one part against one input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub time_us: u64,
+    pub input_hash: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub explanation: Vec<String>,
+    /// answered from the cache, `time_us` is from when it was solved
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cached: bool,
+    /// why there's no answer, e.g. the part timed out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<String>,
+}
+
+/// the workspace root, where each day has its own folder
+pub fn default_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner should be inside the workspace")
+        .to_path_buf()
+}
+
+pub fn input_path(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("day{}", day)).join("input.txt")
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Unreadable(PathBuf, io::Error),
+    /// only the locked copy is there, and it couldn't be decrypted
+    Locked(PathBuf, String),
+    Irregular(PathBuf, Vec<Irregularity>),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Unreadable(path, e) => {
+                write!(f, "{}: {}", path.display(), e)
+            }
+            InputError::Locked(path, e) => {
+                write!(f, "{}: {}", store::locked_path(path).display(), e)
+            }
+            InputError::Irregular(path, irregularities) => {
+                write!(f, "{}:", path.display())?;
+                for i in irregularities {
+                    write!(f, " {};", i)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// reads and normalizes a day's input. in strict mode, an input that
+/// would have needed normalizing is an error instead
+pub fn load_input(
+    root: &Path,
+    day: u8,
+    strict: bool,
+) -> Result<String, InputError> {
+    load_file(&input_path(root, day), strict)
+}
+
+/// like `load_input`, for an input that isn't in the usual place. an
+/// input that's only in the store locked is decrypted
+pub fn load_file(path: &Path, strict: bool) -> Result<String, InputError> {
+    let raw = if !path.exists() && store::locked_path(path).exists() {
+        store::read_locked(path)
+            .map_err(|e| InputError::Locked(path.to_path_buf(), e))?
+    } else {
+        std::fs::read_to_string(path)
+            .map_err(|e| InputError::Unreadable(path.to_path_buf(), e))?
+    };
+    if strict {
+        normalize_strict(&raw)
+            .map_err(|i| InputError::Irregular(path.to_path_buf(), i))
+    } else {
+        Ok(normalize(&raw))
+    }
+}
+
+/// sha256 of the input, so runs on the same input can be matched up
+/// across machines
+pub fn hash(input: &str) -> String {
+    let digest = Sha256::digest(input.as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// solves the part, and if asked for, explains the answer afterwards.
+/// explaining isn't included in the time. a panic in either is caught and
+/// becomes the run's failure
+pub fn run(solver: &Solver, input: &str, explain: bool) -> Run {
+    let start = Instant::now();
+    let outcome = panics::catch(|| (solver.solve)(input));
+    let outcome = outcome
+        .map_err(|panic| panic.to_string())
+        .and_then(|answer| answer.submission());
+    finish(solver, input, explain, outcome, start.elapsed())
+}
+
+/// like `run`, but gives up on the part after `timeout`. parts that
+/// report progress pass each update on to `on_progress`, and are cancelled
+/// cleanly at the first one after time's up. any other part can't be
+/// stopped, see `cancellable`, so it's reported as timed out and left to
+/// finish in the background
+pub fn run_limited(
+    solver: &Solver,
+    input: &str,
+    explain: bool,
+    timeout: Option<Duration>,
+    on_progress: &mut dyn FnMut(usize, usize),
+) -> Run {
+    let Some(solve) = solver.progress else {
+        return match timeout {
+            Some(timeout) => run_on_thread(solver, input, explain, timeout),
+            None => run(solver, input, explain),
+        };
+    };
+    let start = Instant::now();
+    let outcome = panics::catch(|| {
+        solve(input, &mut |done, total| {
+            on_progress(done, total);
+            timeout.is_none_or(|timeout| start.elapsed() < timeout)
+        })
+    });
+    let outcome = match outcome {
+        Ok(Ok(answer)) => answer.submission(),
+        Ok(Err(Cancelled { done, total })) => {
+            Err(format!("timed out after {} of {}", done, total))
+        }
+        Err(panic) => Err(panic.to_string()),
+    };
+    finish(solver, input, explain, outcome, start.elapsed())
+}
+
+/// `run` on a thread of its own, waiting no longer than `timeout` for it.
+/// there's no stopping the thread, so if it's still going it's abandoned
+fn run_on_thread(
+    solver: &Solver,
+    input: &str,
+    explain: bool,
+    timeout: Duration,
+) -> Run {
+    let (tx, rx) = mpsc::channel();
+    let (solver, owned) = (*solver, input.to_string());
+    thread::spawn(move || {
+        // nobody's listening any more if it took too long
+        let _ = tx.send(run(&solver, &owned, explain));
+    });
+    rx.recv_timeout(timeout).unwrap_or_else(|_| Run {
+        day: solver.day,
+        part: solver.part,
+        answer: String::new(),
+        time_us: timeout.as_micros() as u64,
+        input_hash: hash(input),
+        explanation: Vec::new(),
+        cached: false,
+        failure: Some(format!(
+            "timed out after {:?}, and is still running",
+            timeout
+        )),
+    })
+}
+
+/// whether `run_limited` can stop the part when it runs out of time,
+/// rather than leaving it running
+pub fn cancellable(solver: &Solver) -> bool {
+    solver.progress.is_some()
+}
+
+fn finish(
+    solver: &Solver,
+    input: &str,
+    explain: bool,
+    outcome: Result<String, String>,
+    elapsed: Duration,
+) -> Run {
+    let (outcome, explanation) = match (solver.explain, outcome) {
+        (Some(explain_fn), Ok(answer)) if explain => {
+            match panics::catch(|| explain_fn(input)) {
+                Ok(explanation) => (Ok(answer), explanation),
+                Err(panic) => (Err(format!("explaining it {}", panic)), vec![]),
+            }
+        }
+        (_, outcome) => (outcome, Vec::new()),
+    };
+    let (answer, failure) = match outcome {
+        Ok(answer) => (answer, None),
+        Err(failure) => (String::new(), Some(failure)),
+    };
+    Run {
+        day: solver.day,
+        part: solver.part,
+        answer,
+        time_us: elapsed.as_micros() as u64,
+        input_hash: hash(input),
+        explanation,
+        cached: false,
+        failure,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use utils::answer::Answer;
+
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(
+            hash(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_ne!(hash("1 2 3"), hash("1 2 4"));
+    }
+
+    #[test]
+    fn test_load_input() {
+        let root = TestDir::new("load_input");
+        std::fs::create_dir_all(root.join("day1")).unwrap();
+        std::fs::write(root.join("day1/input.txt"), "3   4\r\n4   3\r\n")
+            .unwrap();
+
+        assert_eq!(load_input(&root, 1, false).unwrap(), "3   4\n4   3");
+        match load_input(&root, 1, true) {
+            Err(InputError::Irregular(_, irregularities)) => {
+                assert_eq!(irregularities.len(), 2)
+            }
+            res => panic!("expected irregularities, got {:?}", res),
+        }
+        assert!(matches!(
+            load_input(&root, 2, false),
+            Err(InputError::Unreadable(_, _))
+        ));
+    }
+
+    fn slow(_: &str) -> Answer {
+        thread::sleep(Duration::from_millis(50));
+        "done".into()
+    }
+
+    #[test]
+    fn test_run_limited() {
+        let solver = &crate::days::select(Some(9), Some(1))[0];
+        let run = run_limited(solver, "12345", false, None, &mut |_, _| {});
+        assert_eq!(run.answer, "60");
+        assert_eq!(run.failure, None);
+
+        let solver = Solver {
+            day: 1,
+            part: 1,
+            solve: slow,
+            explain: None,
+            progress: Some(|_, progress| {
+                for i in 1..=1000 {
+                    progress.update(i, 1000)?;
+                    thread::sleep(Duration::from_millis(1));
+                }
+                Ok("done".into())
+            }),
+            budget: crate::days::BUDGET,
+        };
+        let mut updates = 0;
+        let timeout = Some(Duration::from_millis(20));
+        let run =
+            run_limited(&solver, "", false, timeout, &mut |_, _| updates += 1);
+        let failure = run.failure.unwrap();
+        assert!(failure.starts_with("timed out after "), "{}", failure);
+        assert!(failure.ends_with(" of 1000"), "{}", failure);
+        assert!(updates > 0 && updates < 1000);
+        assert_eq!(run.answer, "");
+
+        // without progress it can't be stopped, only given up on
+        let solver = Solver {
+            progress: None,
+            ..solver
+        };
+        assert!(!cancellable(&solver));
+        let run = run_limited(&solver, "", false, timeout, &mut |_, _| {});
+        assert_eq!(
+            run.failure.as_deref(),
+            Some("timed out after 20ms, and is still running")
+        );
+        assert_eq!(run.answer, "");
+        let timeout = Some(Duration::from_secs(10));
+        let run = run_limited(&solver, "", false, timeout, &mut |_, _| {});
+        assert_eq!(run.failure, None);
+        assert_eq!(run.answer, "done");
+    }
+
+    #[test]
+    fn test_panic() {
+        let solver = Solver {
+            day: 1,
+            part: 1,
+            solve: |input| input.parse::<u32>().unwrap().into(),
+            explain: None,
+            progress: None,
+            budget: crate::days::BUDGET,
+        };
+        assert_eq!(run(&solver, "12", false).answer, "12");
+        for run in [
+            run(&solver, "x", false),
+            run_limited(&solver, "x", false, None, &mut |_, _| {}),
+        ] {
+            let failure = run.failure.unwrap();
+            assert!(failure.starts_with("panicked at runner/src/run.rs:"));
+            assert!(failure.ends_with("InvalidDigit }"), "{}", failure);
+        }
+
+        let solver = Solver {
+            explain: Some(|input| vec![input[5..].to_string()]),
+            ..solver
+        };
+        let run = run(&solver, "12", true);
+        assert_eq!(run.answer, "");
+        assert!(run.explanation.is_empty());
+        let failure = run.failure.unwrap();
+        assert!(
+            failure.starts_with("explaining it panicked at runner/src/run.rs:"),
+            "{}",
+            failure
+        );
+    }
+
+    #[test]
+    fn test_input_path() {
+        let path = input_path(Path::new("/aoc"), 7);
+        assert_eq!(path, PathBuf::from("/aoc/day7/input.txt"));
+    }
+}
@@ -0,0 +1,320 @@
+// a small local http server, so other tools can solve inputs without
+// shelling out to the day binaries.
+//
+//   POST /2024/day/{n}/part/{p}[?explain][&strict]
+//
+// with the puzzle input as the body. responds with the same json as
+// `run --format json` for a single part, with an error status if the part
+// failed. each request is solved on its own thread, so a slow one doesn't
+// hold up the rest, but only so many at once
+
+use std::{
+    error::Error,
+    io::Read,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+use utils::input::{normalize, normalize_strict};
+
+use crate::{days, run};
+
+const YEAR: &str = "2024";
+/// the most requests solved at once, any more are turned away with 503
+const MAX_SOLVES: usize = 8;
+/// far bigger than any puzzle input, bigger bodies are turned away with 413
+const MAX_BODY: u64 = 1 << 20;
+
+/// the parts of the url we care about
+#[derive(Debug, PartialEq)]
+struct Route {
+    day: u8,
+    part: u8,
+    explain: bool,
+    strict: bool,
+}
+
+/// one of the `MAX_SOLVES` requests that can be solved at once, given back
+/// when it's dropped
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    fn take(in_use: &Arc<AtomicUsize>) -> Option<Slot> {
+        in_use
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < MAX_SOLVES).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| Slot(in_use.clone()))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// serves until killed, giving up on any part that can be stopped once it's
+/// been going for `timeout`. parts that can't be stopped are left to finish
+pub fn serve(addr: &str, timeout: Duration) -> Result<(), Box<dyn Error>> {
+    let server = Server::http(addr).map_err(|e| e as Box<dyn Error>)?;
+    println!("listening on http://{}", server.server_addr());
+    let in_use = Arc::new(AtomicUsize::new(0));
+    for request in server.incoming_requests() {
+        match Slot::take(&in_use) {
+            Some(slot) => {
+                thread::spawn(move || {
+                    respond(request, timeout);
+                    drop(slot);
+                });
+            }
+            None => reply(request, error(503, "too busy, try again later")),
+        }
+    }
+    Ok(())
+}
+
+fn respond(mut request: Request, timeout: Duration) {
+    let response = match read_body(request.as_reader()) {
+        Ok(body) => handle(request.method(), request.url(), &body, timeout),
+        Err(response) => response,
+    };
+    reply(request, response);
+}
+
+/// the body, unless it's too big or not text
+fn read_body(reader: impl Read) -> Result<String, (u16, serde_json::Value)> {
+    let mut body = String::new();
+    reader
+        .take(MAX_BODY + 1)
+        .read_to_string(&mut body)
+        .map_err(|e| error(400, &format!("couldn't read body: {}", e)))?;
+    if body.len() as u64 > MAX_BODY {
+        return Err(error(
+            413,
+            &format!("inputs can be at most {} bytes", MAX_BODY),
+        ));
+    }
+    Ok(body)
+}
+
+fn reply(request: Request, (status, json): (u16, serde_json::Value)) {
+    println!("{} {} -> {}", request.method(), request.url(), status);
+    let header =
+        Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(json.to_string())
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(e) = request.respond(response) {
+        eprintln!("couldn't respond: {}", e);
+    }
+}
+
+fn handle(
+    method: &Method,
+    url: &str,
+    body: &str,
+    timeout: Duration,
+) -> (u16, serde_json::Value) {
+    let Some(route) = parse_route(url) else {
+        return error(404, &format!("no such route: {}", url));
+    };
+    if *method != Method::Post {
+        return error(405, "post the puzzle input to this url");
+    }
+    let Some(solver) = days::select(Some(route.day), Some(route.part))
+        .into_iter()
+        .next()
+    else {
+        return error(
+            404,
+            &format!("day {} part {} isn't solved", route.day, route.part),
+        );
+    };
+
+    let input = if route.strict {
+        match normalize_strict(body) {
+            Ok(input) => input,
+            Err(irregularities) => {
+                let irregularities: Vec<String> =
+                    irregularities.iter().map(ToString::to_string).collect();
+                return (
+                    400,
+                    json!({ "error": "irregular input", "irregularities": irregularities }),
+                );
+            }
+        }
+    } else {
+        normalize(body)
+    };
+
+    // a part that can't be stopped would only be abandoned, still using up
+    // its slot and a core, so it's left to finish instead
+    let timeout = run::cancellable(solver).then_some(timeout);
+    let run = run::run_limited(
+        solver,
+        &input,
+        route.explain,
+        timeout,
+        &mut |_, _| {},
+    );
+    let status = match &run.failure {
+        None => 200,
+        Some(failure) if failure.starts_with("timed out") => 504,
+        // most likely the input isn't one the part can make sense of
+        Some(_) => 422,
+    };
+    (status, serde_json::to_value(run).unwrap())
+}
+
+fn parse_route(url: &str) -> Option<Route> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let [year, "day", day, "part", part] = segments[..] else {
+        return None;
+    };
+    if year != YEAR {
+        return None;
+    }
+    let flags: Vec<&str> = query.split('&').collect();
+    let flag = |name: &str| {
+        flags.iter().any(|f| {
+            *f == name
+                || f.strip_prefix(name)
+                    .and_then(|v| v.strip_prefix('='))
+                    .is_some_and(|v| v == "true" || v == "1")
+        })
+    };
+    Some(Route {
+        day: day.parse().ok()?,
+        part: part.parse().ok()?,
+        explain: flag("explain"),
+        strict: flag("strict"),
+    })
+}
+
+fn error(status: u16, message: &str) -> (u16, serde_json::Value) {
+    (status, json!({ "error": message }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_route() {
+        assert_eq!(
+            parse_route("/2024/day/7/part/2"),
+            Some(Route {
+                day: 7,
+                part: 2,
+                explain: false,
+                strict: false
+            })
+        );
+        assert_eq!(
+            parse_route("/2024/day/12/part/1?explain&strict=true"),
+            Some(Route {
+                day: 12,
+                part: 1,
+                explain: true,
+                strict: true
+            })
+        );
+        assert_eq!(
+            parse_route("/2024/day/7/part/2?explain=false").map(|r| r.explain),
+            Some(false)
+        );
+        assert_eq!(parse_route("/2023/day/7/part/2"), None);
+        assert_eq!(parse_route("/2024/day/x/part/2"), None);
+        assert_eq!(parse_route("/2024/day/7"), None);
+    }
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn test_handle() {
+        let (status, json) = handle(
+            &Method::Post,
+            "/2024/day/9/part/2",
+            "2333133121414131402\n",
+            TIMEOUT,
+        );
+        assert_eq!(status, 200);
+        assert_eq!(json["answer"], "2858");
+        assert_eq!(json["day"], 9);
+
+        let (status, _) = handle(
+            &Method::Get,
+            "/2024/day/9/part/2",
+            "2333133121414131402",
+            TIMEOUT,
+        );
+        assert_eq!(status, 405);
+
+        let (status, _) =
+            handle(&Method::Post, "/2024/day/25/part/1", "", TIMEOUT);
+        assert_eq!(status, 404);
+
+        let (status, json) = handle(
+            &Method::Post,
+            "/2024/day/9/part/1?strict",
+            "2333133121414131402 \n",
+            TIMEOUT,
+        );
+        assert_eq!(status, 400);
+        assert_eq!(json["irregularities"][0], "line 1 has trailing whitespace");
+    }
+
+    #[test]
+    fn test_handle_failure() {
+        let (status, json) =
+            handle(&Method::Post, "/2024/day/13/part/1", "nonsense", TIMEOUT);
+        assert_eq!(status, 422);
+        assert!(json["failure"].as_str().unwrap().starts_with("panicked"));
+
+        let mut map = vec![".".repeat(60); 60];
+        map[59] = format!("^{}", ".".repeat(59));
+        let map = map.join("\n");
+        let (status, json) =
+            handle(&Method::Post, "/2024/day/6/part/2", &map, Duration::ZERO);
+        assert_eq!(status, 504);
+        assert!(json["failure"].as_str().unwrap().starts_with("timed out"));
+
+        // day 9 can't be stopped, so it isn't timed out either
+        let (status, json) = handle(
+            &Method::Post,
+            "/2024/day/9/part/1",
+            "12345",
+            Duration::ZERO,
+        );
+        assert_eq!(status, 200);
+        assert_eq!(json["answer"], "60");
+    }
+
+    #[test]
+    fn test_read_body() {
+        assert_eq!(read_body("12345".as_bytes()).unwrap(), "12345");
+        let big = "1".repeat(MAX_BODY as usize + 1);
+        assert_eq!(read_body(big.as_bytes()).unwrap_err().0, 413);
+        assert_eq!(read_body(&[0xff, 0xfe][..]).unwrap_err().0, 400);
+    }
+
+    #[test]
+    fn test_slot() {
+        let in_use = Arc::new(AtomicUsize::new(0));
+        let slots: Vec<Slot> =
+            (0..MAX_SOLVES).map_while(|_| Slot::take(&in_use)).collect();
+        assert_eq!(slots.len(), MAX_SOLVES);
+        assert!(Slot::take(&in_use).is_none());
+        drop(slots);
+        assert!(Slot::take(&in_use).is_some());
+    }
+}
@@ -0,0 +1,244 @@
+// how far along each day of the calendar is, without solving anything:
+// which parts exist, whether their cached answers match the known ones,
+// and how long they took the last time they were benchmarked
+
+use std::{path::Path, time::Duration};
+
+use crate::{
+    cache::Cache,
+    days,
+    history::Entry,
+    output,
+    run::{self, input_path, Run},
+    store::{self, locked_path},
+    watch,
+};
+
+pub enum Part {
+    /// there's no solver for it
+    Missing,
+    /// there's a solver, but no answer cached for the current input and
+    /// source
+    Unsolved,
+    /// answered, with no known answer to check against
+    Solved(Run),
+    Verified(Run),
+    Wrong(Run),
+}
+
+pub enum Input {
+    Missing,
+    Plain,
+    Locked,
+}
+
+pub struct Day {
+    pub day: u8,
+    pub parts: [Part; 2],
+    /// each part's latest benchmarked median on the current input
+    pub times_us: [Option<u64>; 2],
+    pub fixtures: usize,
+    pub input: Input,
+}
+
+impl Part {
+    fn label(&self) -> &'static str {
+        match self {
+            Part::Missing => "-",
+            Part::Unsolved => "unsolved",
+            Part::Solved(_) => "solved",
+            Part::Verified(_) => "verified",
+            Part::Wrong(_) => "wrong",
+        }
+    }
+}
+
+fn time(time_us: Option<u64>) -> String {
+    time_us.map_or(String::new(), |us| {
+        format!("{:?}", Duration::from_micros(us))
+    })
+}
+
+/// every day of the calendar, answered from the cache and timed from the
+/// benchmark history
+pub fn status(root: &Path, cache: &mut Cache, history: &[Entry]) -> Vec<Day> {
+    (1..=25)
+        .map(|day| status_of(root, cache, history, day))
+        .collect()
+}
+
+fn status_of(
+    root: &Path,
+    cache: &mut Cache,
+    history: &[Entry],
+    day: u8,
+) -> Day {
+    let path = input_path(root, day);
+    let input = if path.exists() {
+        Input::Plain
+    } else if locked_path(&path).exists() {
+        Input::Locked
+    } else {
+        Input::Missing
+    };
+    let input_hash = match input {
+        Input::Missing => None,
+        _ => run::load_input(root, day, false)
+            .ok()
+            .map(|i| run::hash(&i)),
+    };
+    let answers = store::answers(root, day).unwrap_or_default();
+
+    let mut part = |part: u8| {
+        if days::select(Some(day), Some(part)).is_empty() {
+            return Part::Missing;
+        }
+        let Some(run) = input_hash
+            .as_ref()
+            .and_then(|hash| cache.get(day, part, hash))
+        else {
+            return Part::Unsolved;
+        };
+        match answers.get(part as usize - 1).filter(|a| !a.is_empty()) {
+            None => Part::Solved(run),
+            Some(answer) if *answer == run.answer => Part::Verified(run),
+            Some(_) => Part::Wrong(run),
+        }
+    };
+    let parts = [part(1), part(2)];
+    let time_us = |part: u8| {
+        history
+            .iter()
+            .rev()
+            .find(|e| {
+                e.day == day
+                    && e.part == part
+                    && Some(&e.input_hash) == input_hash.as_ref()
+            })
+            .map(|e| e.median_us)
+    };
+
+    Day {
+        day,
+        parts,
+        times_us: [time_us(1), time_us(2)],
+        fixtures: watch::fixtures(root, day).len(),
+        input,
+    }
+}
+
+/// the calendar as a table, with a star for each verified part
+pub fn table(days: &[Day]) -> String {
+    let mut rows = vec![vec![
+        "day".to_string(),
+        "part 1".to_string(),
+        "time".to_string(),
+        "part 2".to_string(),
+        "time".to_string(),
+        "fixtures".to_string(),
+        "input".to_string(),
+    ]];
+    for d in days {
+        rows.push(vec![
+            d.day.to_string(),
+            d.parts[0].label().to_string(),
+            time(d.times_us[0]),
+            d.parts[1].label().to_string(),
+            time(d.times_us[1]),
+            d.fixtures.to_string(),
+            match d.input {
+                Input::Missing => "-",
+                Input::Plain => "plain",
+                Input::Locked => "locked",
+            }
+            .to_string(),
+        ]);
+    }
+    let stars = days
+        .iter()
+        .flat_map(|d| &d.parts)
+        .filter(|p| matches!(p, Part::Verified(_)))
+        .count();
+    let plural = if stars == 1 { "" } else { "s" };
+    format!("{}{} star{}\n", output::align(&rows), stars, plural)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    #[test]
+    fn test_status() {
+        let root = TestDir::new("status");
+        std::fs::create_dir_all(root.join("day9")).unwrap();
+        std::fs::create_dir_all(root.join("day11")).unwrap();
+        let input = "2333133121414131402";
+        std::fs::write(root.join("day9/input.txt"), input).unwrap();
+        std::fs::write(root.join("day9/answers.txt"), "1928\n2859\n").unwrap();
+        std::fs::write(root.join("day9/test_input.txt"), "12345").unwrap();
+        std::fs::write(root.join("day11/input.txt"), "125 17").unwrap();
+
+        let mut cache = Cache::open(&root.join("cache.json"), &root);
+        for (day, part, input, answer) in [
+            (9, 1, input, "1928"),
+            (9, 2, input, "2858"),
+            (11, 1, "125 17", "55312"),
+        ] {
+            cache.insert(&Run {
+                day,
+                part,
+                answer: answer.to_string(),
+                time_us: 7,
+                input_hash: run::hash(input),
+                explanation: vec![],
+                cached: false,
+                failure: None,
+            });
+        }
+
+        let entry = |day, part, input, median_us| Entry {
+            commit: "abc".to_string(),
+            timestamp: 0,
+            day,
+            part,
+            input_hash: run::hash(input),
+            median_us,
+            allocations: 0,
+        };
+        let history = vec![
+            entry(9, 1, input, 2000),
+            entry(9, 1, input, 1500),
+            entry(9, 2, input, 1500),
+            // benchmarked on another input, so not shown
+            entry(11, 2, "0 1", 900),
+            entry(11, 1, "125 17", 1500),
+        ];
+
+        let days = status(&root, &mut cache, &history);
+        assert_eq!(days.len(), 25);
+        let table = table(&days);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[0],
+            "day  part 1    time   part 2    time   fixtures  input"
+        );
+        assert_eq!(
+            lines[1],
+            "1    unsolved         unsolved         0         -"
+        );
+        assert_eq!(
+            lines[9],
+            "9    verified  1.5ms  wrong     1.5ms  1         plain"
+        );
+        assert_eq!(
+            lines[11],
+            "11   solved    1.5ms  unsolved         0         plain"
+        );
+        assert_eq!(
+            lines[20],
+            "20   -                -                0         -"
+        );
+        assert_eq!(lines[26], "1 star");
+    }
+}
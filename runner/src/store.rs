@@ -0,0 +1,342 @@
+// puzzle inputs and answers, kept encrypted so they can be committed. each
+// day's input.txt and answers.txt are locked into input.txt.enc and
+// answers.txt.enc, and the plaintext is ignored by git. the runner reads
+// the locked files directly when the plaintext isn't there
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key,
+};
+
+/// the files in each day's folder that belong in the store
+pub const FILES: [&str; 2] = ["input.txt", "answers.txt"];
+/// where the passphrase is read from, before asking for it
+pub const PASSPHRASE_VAR: &str = "AOC_PASSPHRASE";
+
+const MAGIC: &[u8; 8] = b"AOCLOCK1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// keys derived from one passphrase, by salt. deriving is deliberately
+/// slow, and every file locked at the same time shares a salt
+pub struct Keys {
+    passphrase: String,
+    derived: HashMap<[u8; SALT_LEN], Key>,
+}
+
+impl Keys {
+    pub fn new(passphrase: &str) -> Keys {
+        Keys {
+            passphrase: passphrase.to_string(),
+            derived: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, salt: [u8; SALT_LEN]) -> &Key {
+        self.derived.entry(salt).or_insert_with(|| {
+            let mut key = Key::default();
+            Argon2::default()
+                .hash_password_into(self.passphrase.as_bytes(), &salt, &mut key)
+                .expect("the salt and key lengths are valid for argon2");
+            key
+        })
+    }
+}
+
+/// the passphrase from $AOC_PASSPHRASE, or else asked for on the terminal
+pub fn passphrase() -> std::io::Result<String> {
+    match std::env::var(PASSPHRASE_VAR) {
+        Ok(passphrase) => Ok(passphrase),
+        Err(_) => rpassword::prompt_password("store passphrase: "),
+    }
+}
+
+/// like `passphrase`, but asks twice when it's asked for, since nothing's
+/// locked yet to check a mistyped one against
+pub fn new_passphrase() -> Result<String, Box<dyn Error>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_VAR) {
+        return Ok(passphrase);
+    }
+    let passphrase = rpassword::prompt_password("new store passphrase: ")?;
+    if rpassword::prompt_password("and again: ")? != passphrase {
+        return Err("the passphrases don't match".into());
+    }
+    Ok(passphrase)
+}
+
+/// where `path` goes when it's locked
+pub fn locked_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".enc");
+    path.with_file_name(name)
+}
+
+pub fn encrypt(keys: &mut Keys, salt: [u8; SALT_LEN], plain: &[u8]) -> Vec<u8> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let cipher = ChaCha20Poly1305::new(keys.get(salt));
+    let sealed = cipher
+        .encrypt(&nonce, plain)
+        .expect("encrypting into a vec can't fail");
+    [MAGIC.as_slice(), &salt, &nonce, &sealed].concat()
+}
+
+pub fn decrypt(keys: &mut Keys, locked: &[u8]) -> Result<Vec<u8>, String> {
+    let header = MAGIC.len() + SALT_LEN + NONCE_LEN;
+    if locked.len() < header || !locked.starts_with(MAGIC) {
+        return Err("not a locked file".to_string());
+    }
+    let (salt, rest) = locked[MAGIC.len()..].split_at(SALT_LEN);
+    let (nonce, sealed) = rest.split_at(NONCE_LEN);
+    let cipher = ChaCha20Poly1305::new(keys.get(salt.try_into().unwrap()));
+    cipher
+        .decrypt(nonce.into(), sealed)
+        .map_err(|_| "wrong passphrase, or the file is corrupt".to_string())
+}
+
+/// decrypts the locked copy of `path`, asking for the passphrase the
+/// first time it's needed
+pub fn read_locked(path: &Path) -> Result<String, String> {
+    static KEYS: OnceLock<Mutex<Keys>> = OnceLock::new();
+    let locked = std::fs::read(locked_path(path)).map_err(|e| e.to_string())?;
+    let keys = match KEYS.get() {
+        Some(keys) => keys,
+        None => {
+            let passphrase = passphrase().map_err(|e| e.to_string())?;
+            KEYS.get_or_init(|| Mutex::new(Keys::new(&passphrase)))
+        }
+    };
+    let plain = decrypt(&mut keys.lock().unwrap(), &locked)?;
+    String::from_utf8(plain).map_err(|e| e.to_string())
+}
+
+/// where the day's answers.txt goes, before it's locked
+pub fn answers_path(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("day{}", day)).join("answers.txt")
+}
+
+/// the day's known answers, one per line, part 1 first. a blank line is a
+/// part whose answer isn't known yet. locked or not, and none if there
+/// aren't any
+pub fn answers(root: &Path, day: u8) -> Result<Vec<String>, String> {
+    let path = answers_path(root, day);
+    let text = if path.exists() {
+        std::fs::read_to_string(&path).map_err(|e| e.to_string())?
+    } else if locked_path(&path).exists() {
+        read_locked(&path)?
+    } else {
+        return Ok(Vec::new());
+    };
+    Ok(text.lines().map(|l| l.trim().to_string()).collect())
+}
+
+/// every plaintext or locked store file under `root`
+fn store_files(root: &Path) -> Vec<PathBuf> {
+    (1..=25)
+        .flat_map(|day| {
+            let dir = root.join(format!("day{}", day));
+            FILES.map(|file| dir.join(file))
+        })
+        .filter(|path| path.exists() || locked_path(path).exists())
+        .collect()
+}
+
+/// whether anything in the store under `root` has been locked yet
+pub fn has_locked(root: &Path) -> bool {
+    store_files(root).iter().any(|p| locked_path(p).exists())
+}
+
+/// decrypts every locked file, so a wrong passphrase is caught before
+/// anything's written with it
+fn check_passphrase(
+    root: &Path,
+    keys: &mut Keys,
+) -> Result<HashMap<PathBuf, Vec<u8>>, Box<dyn Error>> {
+    let mut res = HashMap::new();
+    for path in store_files(root) {
+        let locked = locked_path(&path);
+        let Ok(sealed) = std::fs::read(&locked) else {
+            continue;
+        };
+        let plain = decrypt(keys, &sealed)
+            .map_err(|e| format!("{}: {}", locked.display(), e))?;
+        res.insert(path, plain);
+    }
+    Ok(res)
+}
+
+/// encrypts every plaintext file in the store and removes it. locked
+/// copies that are already up to date are left alone, so that locking
+/// doesn't change files nobody edited. nothing is written unless the
+/// passphrase opens every file that's already locked, and the plaintext
+/// is only removed once its locked copy reads back the same
+pub fn lock(
+    root: &Path,
+    keys: &mut Keys,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let current = check_passphrase(root, keys)?;
+    let mut salt = [0; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let mut locked = Vec::new();
+    for path in store_files(root).into_iter().filter(|p| p.exists()) {
+        let plain = std::fs::read(&path)?;
+        if current.get(&path) != Some(&plain) {
+            std::fs::write(locked_path(&path), encrypt(keys, salt, &plain))?;
+            let written = std::fs::read(locked_path(&path))?;
+            if decrypt(keys, &written).ok() != Some(plain) {
+                return Err(format!(
+                    "{} didn't read back the same, so {} was kept",
+                    locked_path(&path).display(),
+                    path.display()
+                )
+                .into());
+            }
+            locked.push(path.clone());
+        }
+        std::fs::remove_file(&path)?;
+    }
+    Ok(locked)
+}
+
+/// what `unlock` did
+#[derive(Debug, Default, PartialEq)]
+pub struct Unlocked {
+    pub unlocked: Vec<PathBuf>,
+    /// plaintext that's been edited since it was locked, and was left be
+    pub kept: Vec<PathBuf>,
+}
+
+/// decrypts every locked file in the store next to its locked copy. a
+/// plaintext file that differs from its locked copy is only overwritten
+/// if `force` is set
+pub fn unlock(
+    root: &Path,
+    keys: &mut Keys,
+    force: bool,
+) -> Result<Unlocked, Box<dyn Error>> {
+    let mut res = Unlocked::default();
+    for (path, plain) in check_passphrase(root, keys)? {
+        let edited = std::fs::read(&path).is_ok_and(|p| p != plain);
+        if edited && !force {
+            res.kept.push(path);
+            continue;
+        }
+        std::fs::write(&path, plain)?;
+        res.unlocked.push(path);
+    }
+    res.unlocked.sort();
+    res.kept.sort();
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    #[test]
+    fn test_encrypt() {
+        let mut keys = Keys::new("hunter2");
+        let locked = encrypt(&mut keys, [7; SALT_LEN], b"2333133121414131402");
+        assert!(!locked.windows(4).any(|w| w == b"2333"));
+        assert_eq!(
+            decrypt(&mut keys, &locked).unwrap(),
+            b"2333133121414131402"
+        );
+
+        let mut wrong = Keys::new("hunter3");
+        assert!(decrypt(&mut wrong, &locked).is_err());
+        assert!(decrypt(&mut keys, b"2333133121414131402").is_err());
+        let mut tampered = locked.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(decrypt(&mut keys, &tampered).is_err());
+    }
+
+    #[test]
+    fn test_lock() {
+        let root = TestDir::new("store");
+        std::fs::create_dir_all(root.join("day9")).unwrap();
+        std::fs::create_dir_all(root.join("day11")).unwrap();
+        let input = root.join("day9/input.txt");
+        let answers = root.join("day9/answers.txt");
+        std::fs::write(&input, "12345\n").unwrap();
+        std::fs::write(&answers, "60\n132\n").unwrap();
+        std::fs::write(root.join("day11/test_input.txt"), "125 17").unwrap();
+
+        let mut keys = Keys::new("hunter2");
+        assert_eq!(
+            lock(&root, &mut keys).unwrap(),
+            vec![input.clone(), answers.clone()]
+        );
+        assert!(!input.exists() && locked_path(&input).exists());
+        assert!(root.join("day11/test_input.txt").exists());
+
+        assert_eq!(unlock(&root, &mut keys, false).unwrap().unlocked.len(), 2);
+        assert_eq!(std::fs::read_to_string(&input).unwrap(), "12345\n");
+        let before = std::fs::read(locked_path(&input)).unwrap();
+
+        // only what changed is locked again
+        std::fs::write(&answers, "60\n133\n").unwrap();
+        assert_eq!(lock(&root, &mut keys).unwrap(), vec![answers.clone()]);
+        assert_eq!(std::fs::read(locked_path(&input)).unwrap(), before);
+
+        assert!(unlock(&root, &mut Keys::new("hunter3"), false).is_err());
+        assert!(!input.exists());
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let root = TestDir::new("store_wrong");
+        std::fs::create_dir_all(root.join("day9")).unwrap();
+        let input = root.join("day9/input.txt");
+        let answers = root.join("day9/answers.txt");
+        std::fs::write(&input, "12345\n").unwrap();
+        let mut keys = Keys::new("hunter2");
+        assert!(!has_locked(&root));
+        lock(&root, &mut keys).unwrap();
+        assert!(has_locked(&root));
+
+        // a mistyped passphrase doesn't lock anything, or lose anything
+        std::fs::write(&answers, "60\n").unwrap();
+        let before = std::fs::read(locked_path(&input)).unwrap();
+        assert!(lock(&root, &mut Keys::new("hunter3")).is_err());
+        assert!(answers.exists() && !locked_path(&answers).exists());
+        assert_eq!(std::fs::read(locked_path(&input)).unwrap(), before);
+    }
+
+    #[test]
+    fn test_unlock_keeps_edits() {
+        let root = TestDir::new("store_edits");
+        std::fs::create_dir_all(root.join("day9")).unwrap();
+        let input = root.join("day9/input.txt");
+        let answers = root.join("day9/answers.txt");
+        std::fs::write(&input, "12345\n").unwrap();
+        std::fs::write(&answers, "60\n").unwrap();
+        let mut keys = Keys::new("hunter2");
+        lock(&root, &mut keys).unwrap();
+        unlock(&root, &mut keys, false).unwrap();
+
+        std::fs::write(&input, "2333133121414131402\n").unwrap();
+        assert_eq!(
+            unlock(&root, &mut keys, false).unwrap(),
+            Unlocked {
+                unlocked: vec![answers.clone()],
+                kept: vec![input.clone()],
+            }
+        );
+        assert_eq!(
+            std::fs::read_to_string(&input).unwrap(),
+            "2333133121414131402\n"
+        );
+        let res = unlock(&root, &mut keys, true).unwrap();
+        assert_eq!(res.unlocked, vec![answers, input.clone()]);
+        assert_eq!(std::fs::read_to_string(&input).unwrap(), "12345\n");
+    }
+}
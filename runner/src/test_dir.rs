@@ -0,0 +1,37 @@
+// a scratch directory for a test. it's named after the test and the
+// process running it, so runs in parallel can't see each other's files,
+// starts out empty and is removed when the test ends, even if it fails
+
+use std::{
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+pub struct TestDir(PathBuf);
+
+impl TestDir {
+    pub fn new(name: &str) -> TestDir {
+        let path = std::env::temp_dir().join(format!(
+            "runner_test_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        TestDir(path)
+    }
+}
+
+impl Deref for TestDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,213 @@
+// re-runs a day whenever something it depends on changes. every day is
+// compiled into the runner, so it picks up code changes by rebuilding
+// itself and running the new binary
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    cache,
+    run::{default_root, input_path, Run},
+    store,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// example inputs kept next to a day's code, e.g. test_input.txt
+pub fn fixtures(root: &Path, day: u8) -> Vec<PathBuf> {
+    let dir = root.join(format!("day{}", day));
+    let mut res: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name().and_then(|name| name.to_str()).is_some_and(
+                |name| name.starts_with("test_input") && name.ends_with(".txt"),
+            )
+        })
+        .collect();
+    res.sort();
+    res
+}
+
+pub fn watch(root: &Path, day: u8) -> Result<(), Box<dyn Error>> {
+    // looked up before the first rebuild replaces it, after which the
+    // running binary's path no longer exists
+    let exe = std::env::current_exe()?;
+    let mut last_snapshot = None;
+    let mut last_answers: HashMap<(String, u8), String> = HashMap::new();
+    loop {
+        let snapshot = snapshot(&watched_files(root, day));
+        if last_snapshot.as_ref() != Some(&snapshot) {
+            last_snapshot = Some(snapshot);
+            println!("--- day {}", day);
+            if let Err(e) = rebuild() {
+                // most likely a compile error, which cargo has already
+                // printed
+                eprintln!("{}", e);
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+
+            let mut inputs = fixtures(root, day);
+            inputs.push(input_path(root, day));
+            let inputs = inputs.into_iter().filter(|path| {
+                path.exists() || store::locked_path(path).exists()
+            });
+            for input in inputs {
+                let name = input.file_name().unwrap().to_string_lossy();
+                let runs = match run_binary(&exe, root, day, &input) {
+                    Ok(runs) => runs,
+                    Err(e) => {
+                        eprintln!("{}: {}", name, e);
+                        continue;
+                    }
+                };
+                for run in runs {
+                    let key = (name.to_string(), run.part);
+                    println!(
+                        "{}",
+                        describe(&name, &run, last_answers.get(&key))
+                    );
+                    if run.failure.is_none() {
+                        last_answers.insert(key, run.answer);
+                    }
+                }
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// every file that should trigger a re-run when it changes: the same
+/// source files the cache keys answers on, plus the inputs
+fn watched_files(root: &Path, day: u8) -> Vec<PathBuf> {
+    let mut files = cache::source_files(&default_root(), day);
+    let input = input_path(root, day);
+    files.push(store::locked_path(&input));
+    files.push(input);
+    files.extend(fixtures(root, day));
+    files
+}
+
+/// when each file was last modified, `None` if it doesn't exist (yet)
+fn snapshot(files: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut res: Vec<(PathBuf, Option<SystemTime>)> = files
+        .iter()
+        .map(|f| (f.clone(), f.metadata().and_then(|m| m.modified()).ok()))
+        .collect();
+    res.sort();
+    res
+}
+
+/// builds the runner again, with the same profile as the one running
+fn rebuild() -> Result<(), Box<dyn Error>> {
+    let mut cargo = Command::new("cargo");
+    cargo
+        .current_dir(default_root())
+        .args(["build", "-q", "-p", "runner"]);
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    let status = cargo.status()?;
+    if !status.success() {
+        return Err(format!("cargo build exited with {}", status).into());
+    }
+    Ok(())
+}
+
+fn run_binary(
+    exe: &Path,
+    root: &Path,
+    day: u8,
+    input: &Path,
+) -> Result<Vec<Run>, Box<dyn Error>> {
+    let output = Command::new(exe)
+        .arg("run")
+        .arg("--root")
+        .arg(root)
+        .args(["--day", &day.to_string(), "--format", "json", "--input"])
+        .arg(input)
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() && output.stdout.is_empty() {
+        return Err(format!("runner exited with {}", output.status).into());
+    }
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+/// one line per answer, noting how it compares to the previous run
+fn describe(name: &str, run: &Run, previous: Option<&String>) -> String {
+    if let Some(failure) = &run.failure {
+        return format!("{} part {}: {}", name, run.part, failure);
+    }
+    let change = match previous {
+        None => String::new(),
+        Some(p) if *p == run.answer => " (unchanged)".to_string(),
+        Some(p) => format!(" (was {})", p),
+    };
+    format!(
+        "{} part {}: {} in {:?}{}",
+        name,
+        run.part,
+        run.answer,
+        Duration::from_micros(run.time_us),
+        change
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixtures() {
+        let fixtures = fixtures(&default_root(), 13);
+        assert_eq!(fixtures, vec![default_root().join("day13/test_input.txt")]);
+    }
+
+    #[test]
+    fn test_watched_files() {
+        let files = watched_files(&default_root(), 7);
+        let root = default_root().canonicalize().unwrap();
+        assert!(files.contains(&root.join("day7/src/lib.rs")));
+        assert!(files.contains(&root.join("day7/src/equation.rs")));
+        assert!(files.contains(&root.join("utils/src/lib.rs")));
+        assert!(files.contains(&root.join("runner/src/days.rs")));
+        assert!(files.contains(&default_root().join("day7/input.txt")));
+        assert!(files.contains(&default_root().join("day7/input.txt.enc")));
+    }
+
+    #[test]
+    fn test_describe() {
+        let run = Run {
+            day: 9,
+            part: 1,
+            answer: "1928".to_string(),
+            time_us: 12,
+            input_hash: String::new(),
+            explanation: vec![],
+            cached: false,
+            failure: None,
+        };
+        assert_eq!(
+            describe("input.txt", &run, None),
+            "input.txt part 1: 1928 in 12µs"
+        );
+        assert_eq!(
+            describe("input.txt", &run, Some(&"1928".to_string())),
+            "input.txt part 1: 1928 in 12µs (unchanged)"
+        );
+        assert_eq!(
+            describe("input.txt", &run, Some(&"1900".to_string())),
+            "input.txt part 1: 1928 in 12µs (was 1900)"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+// what a part comes up with. most parts count something, but some spell
+// out a word, or draw a picture of one that has to be read
+
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    /// wide enough for any of the integer types the parts return
+    Int(i128),
+    Text(String),
+    /// a picture, one string per row, normalized by `Answer::art`
+    Art(Vec<String>),
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Int(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+/// the one integer type that doesn't fit in an `Int`. the rare one that's
+/// too big is kept as its digits, which print and submit the same
+impl From<u128> for Answer {
+    fn from(n: u128) -> Answer {
+        i128::try_from(n)
+            .map_or_else(|_| Answer::Text(n.to_string()), Answer::Int)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Art(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+impl Answer {
+    /// a picture, with any chars other than space and . counting as lit
+    pub fn art(picture: &str) -> Answer {
+        Answer::Art(normalize_art(picture))
+    }
+
+    /// the answer as it'd be typed in, with art read as letters. answers
+    /// are kept one to a line, so anything that'd take more than one line,
+    /// like art that can't be read, is an error
+    pub fn submission(&self) -> Result<String, String> {
+        let res = match self {
+            Answer::Art(rows) => ocr(rows).ok_or_else(|| {
+                format!("couldn't read the letters in\n{}", self)
+            })?,
+            _ => self.to_string(),
+        };
+        if res.contains('\n') {
+            return Err(format!(
+                "the answer takes more than one line:\n{}",
+                res
+            ));
+        }
+        Ok(res)
+    }
+}
+
+const LIT: char = '#';
+const UNLIT: char = '.';
+
+/// redraws the picture with # and ., without the blank rows above and
+/// below it or the blank columns after it. blank columns before it are
+/// only removed 5 at a time, so letters stay where `ocr` expects them
+pub fn normalize_art(picture: &str) -> Vec<String> {
+    let rows: Vec<Vec<bool>> = picture
+        .lines()
+        .map(|l| l.chars().map(|c| c != ' ' && c != UNLIT).collect())
+        .collect();
+    let lit_rows: Vec<usize> = (0..rows.len())
+        .filter(|y| rows[*y].iter().any(|lit| *lit))
+        .collect();
+    let lit_cols: Vec<usize> = rows
+        .iter()
+        .flat_map(|r| r.iter().enumerate().filter(|(_, lit)| **lit))
+        .map(|(x, _)| x)
+        .collect();
+    let (Some(top), Some(bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        return Vec::new();
+    };
+    let left = lit_cols.iter().min().unwrap() / GLYPH_STRIDE * GLYPH_STRIDE;
+    let right = *lit_cols.iter().max().unwrap();
+    rows[*top..=*bottom]
+        .iter()
+        .map(|r| {
+            (left..=right)
+                .map(|x| if r.get(x) == Some(&true) { LIT } else { UNLIT })
+                .collect()
+        })
+        .collect()
+}
+
+/// each letter's width, plus the blank column after it
+const GLYPH_STRIDE: usize = 5;
+
+/// the 4x6 letters the puzzles draw their answers in
+const FONT: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// reads the letters in a picture, if it's six rows of them with a blank
+/// column after each. pictures that don't start on a letter's first
+/// column are tried shifted right until one does. blank space around the
+/// letters is skipped
+pub fn ocr(rows: &[String]) -> Option<String> {
+    if rows.len() != 6 {
+        return None;
+    }
+    (0..GLYPH_STRIDE).find_map(|shift| {
+        let rows: Vec<Vec<char>> = rows
+            .iter()
+            .map(|r| {
+                std::iter::repeat_n(UNLIT, shift).chain(r.chars()).collect()
+            })
+            .collect();
+        read_letters(&rows)
+    })
+}
+
+fn read_letters(rows: &[Vec<char>]) -> Option<String> {
+    let width = rows.iter().map(Vec::len).max()?;
+    let mut res = String::new();
+    for start in (0..width).step_by(GLYPH_STRIDE) {
+        let glyph: Vec<String> = rows
+            .iter()
+            .map(|r| {
+                (start..start + 4)
+                    .map(|x| r.get(x).copied().unwrap_or(UNLIT))
+                    .collect()
+            })
+            .collect();
+        if glyph.iter().all(|r| !r.contains(LIT)) {
+            continue;
+        }
+        let (letter, _) = FONT.iter().find(|(_, g)| glyph == g)?;
+        res.push(*letter);
+    }
+    (!res.is_empty()).then_some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(-3i32), Answer::Int(-3));
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from(-3i8), Answer::Int(-3));
+        assert_eq!(Answer::from(255u8), Answer::Int(255));
+        assert_eq!(Answer::from(i128::MIN), Answer::Int(i128::MIN));
+        assert_eq!(Answer::from(7u128), Answer::Int(7));
+        let big = Answer::from(u128::MAX);
+        assert_eq!(big, Answer::Text(u128::MAX.to_string()));
+        assert_eq!(big.submission(), Ok(u128::MAX.to_string()));
+        assert_eq!(Answer::from("abc"), Answer::Text("abc".to_string()));
+        assert_eq!(Answer::from(12usize).submission(), Ok("12".to_string()));
+        assert!(Answer::from("a\nb").submission().is_err());
+    }
+
+    #[test]
+    fn test_normalize_art() {
+        let picture = "\n   \n  ##  \n  #   \n        \n";
+        assert_eq!(normalize_art(picture), vec!["..##", "..#."]);
+        let picture = "       ##  \n       #   \n";
+        assert_eq!(normalize_art(picture), vec!["..##", "..#."]);
+        assert_eq!(normalize_art(" . \n"), Vec::<String>::new());
+        assert_eq!(normalize_art("x█\n█."), vec!["##", "#."]);
+    }
+
+    #[test]
+    fn test_ocr() {
+        let picture = "\
+#..#.####.#....#.....##.
+#..#.#....#....#....#..#
+####.###..#....#....#..#
+#..#.#....#....#....#..#
+#..#.#....#....#....#..#
+#..#.####.####.####..##.";
+        let art = Answer::art(picture);
+        assert_eq!(art.submission(), Ok("HELLO".to_string()));
+        assert_eq!(art.to_string(), picture);
+
+        // the same letters drawn with other chars, and space around them
+        let drawn = picture.replace('.', " ").replace('#', "█");
+        assert_eq!(
+            Answer::art(&format!("\n{}\n", drawn)).submission(),
+            Ok("HELLO".to_string())
+        );
+
+        let unreadable = Answer::art("#.#\n.#.\n#.#");
+        assert!(unreadable.submission().is_err());
+    }
+
+    #[test]
+    fn test_ocr_blank_first_column() {
+        // I's first column is blank, which mustn't shift the letters after
+        let picture = "\
+.###.#...
+..#..#...
+..#..#...
+..#..#...
+..#..#...
+.###.####";
+        assert_eq!(Answer::art(picture).submission(), Ok("IL".to_string()));
+
+        // however far in it starts
+        let indented: Vec<String> =
+            picture.lines().map(|l| format!("   {}", l)).collect();
+        let rows = normalize_art(&indented.join("\n"));
+        assert_eq!(ocr(&rows), Some("IL".to_string()));
+        let rows: Vec<String> =
+            rows.iter().map(|r| r[3..].to_string()).collect();
+        assert_eq!(ocr(&rows), Some("IL".to_string()));
+    }
+}
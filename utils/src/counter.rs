@@ -0,0 +1,185 @@
+use std::collections::{btree_map, BTreeMap};
+
+/// a multiset, keeping track of how many times each key has been added.
+/// keys are kept sorted, so iterating always goes from smallest to largest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<K: Ord>(BTreeMap<K, usize>);
+
+impl<K: Ord> Default for Counter<K> {
+    fn default() -> Self {
+        Counter(BTreeMap::new())
+    }
+}
+
+impl<K: Ord> Counter<K> {
+    pub fn new() -> Counter<K> {
+        Counter::default()
+    }
+
+    pub fn add(&mut self, key: K) {
+        self.add_n(key, 1);
+    }
+
+    pub fn add_n(&mut self, key: K, n: usize) {
+        if n > 0 {
+            *self.0.entry(key).or_insert(0) += n;
+        }
+    }
+
+    /// how many times the key has been added, 0 if it never was
+    pub fn get(&self, key: &K) -> usize {
+        self.0.get(key).copied().unwrap_or(0)
+    }
+
+    /// adds every count from the other counter into this one
+    pub fn merge(&mut self, other: Counter<K>) {
+        for (key, n) in other {
+            self.add_n(key, n);
+        }
+    }
+
+    /// the sum of all the counts
+    pub fn total(&self) -> usize {
+        self.0.values().sum()
+    }
+
+    /// the number of distinct keys
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// keys with their counts, highest count first.
+    /// ties are broken by key, so the order is stable
+    pub fn most_common(&self) -> Vec<(&K, usize)> {
+        let mut v: Vec<(&K, usize)> = self.iter().collect();
+        v.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        v
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, usize)> {
+        self.0.iter().map(|(k, n)| (k, *n))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.0.keys()
+    }
+
+    pub fn counts(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.values().copied()
+    }
+
+    /// transforms every key, adding up the counts of keys that end up the same
+    pub fn map_keys<K2, F>(self, f: F) -> Counter<K2>
+    where
+        K2: Ord,
+        F: Fn(K) -> K2,
+    {
+        self.flat_map_keys(|k| [f(k)])
+    }
+
+    /// replaces every key with any number of new keys, each of which
+    /// inherits the count of the key it came from
+    pub fn flat_map_keys<K2, I, F>(self, f: F) -> Counter<K2>
+    where
+        K2: Ord,
+        I: IntoIterator<Item = K2>,
+        F: Fn(K) -> I,
+    {
+        let mut res = Counter::new();
+        for (key, n) in self {
+            for new_key in f(key) {
+                res.add_n(new_key, n);
+            }
+        }
+        res
+    }
+}
+
+impl<K: Ord> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        iter.into_iter().for_each(|k| counter.add(k));
+        counter
+    }
+}
+
+impl<K: Ord, const N: usize> From<[(K, usize); N]> for Counter<K> {
+    fn from(counts: [(K, usize); N]) -> Self {
+        let mut counter = Counter::new();
+        for (k, n) in counts {
+            counter.add_n(k, n);
+        }
+        counter
+    }
+}
+
+impl<K: Ord> IntoIterator for Counter<K> {
+    type Item = (K, usize);
+    type IntoIter = btree_map::IntoIter<K, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() {
+        let mut c = Counter::new();
+        c.add('a');
+        c.add('b');
+        c.add('a');
+        c.add_n('c', 3);
+        c.add_n('d', 0);
+        assert_eq!(c.get(&'a'), 2);
+        assert_eq!(c.get(&'b'), 1);
+        assert_eq!(c.get(&'c'), 3);
+        assert_eq!(c.get(&'d'), 0);
+        assert_eq!(c.len(), 3);
+        assert_eq!(c.total(), 6);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let c: Counter<i32> = [3, 4, 2, 1, 3, 3].into_iter().collect();
+        assert_eq!(c, Counter::from([(1, 1), (2, 1), (3, 3), (4, 1)]));
+        let sorted: Vec<(&i32, usize)> = c.iter().collect();
+        assert_eq!(sorted, vec![(&1, 1), (&2, 1), (&3, 3), (&4, 1)]);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = Counter::from([("x", 1), ("y", 2)]);
+        a.merge(Counter::from([("y", 3), ("z", 4)]));
+        assert_eq!(a, Counter::from([("x", 1), ("y", 5), ("z", 4)]));
+    }
+
+    #[test]
+    fn test_most_common() {
+        let c = Counter::from([('a', 1), ('b', 3), ('c', 3), ('d', 2)]);
+        assert_eq!(
+            c.most_common(),
+            vec![(&'b', 3), (&'c', 3), (&'d', 2), (&'a', 1)]
+        );
+    }
+
+    #[test]
+    fn test_map_keys() {
+        let c = Counter::from([(1, 2), (2, 3), (3, 4)]);
+        assert_eq!(
+            c.clone().map_keys(|k| k % 2),
+            Counter::from([(0, 3), (1, 6)])
+        );
+        assert_eq!(
+            c.flat_map_keys(|k| vec![k; k]),
+            Counter::from([(1, 2), (2, 6), (3, 12)])
+        );
+    }
+}
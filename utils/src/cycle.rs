@@ -0,0 +1,203 @@
+// cycle detection for deterministic state machines, i.e. anything where the
+// next state only depends on the current one. every finite state machine
+// eventually repeats, so these all terminate as long as the state space is finite
+
+use std::{collections::HashMap, hash::Hash};
+
+/// where a sequence of states starts repeating.
+/// `start` is the index of the first state that's part of the cycle,
+/// `length` is how many states are in the cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// replays the sequence, returning every state before the cycle
+    /// followed by one full lap of it
+    pub fn states<S, F>(&self, init: S, f: F) -> Vec<S>
+    where
+        S: Clone,
+        F: Fn(S) -> S,
+    {
+        let mut states = Vec::with_capacity(self.start + self.length);
+        let mut state = init;
+        for _ in 0..self.start + self.length {
+            states.push(state.clone());
+            state = f(state);
+        }
+        states
+    }
+
+    /// maps the n-th step of the sequence onto the index of the
+    /// equivalent state returned by `states`
+    pub fn index_of(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// floyd's tortoise and hare, constant memory
+pub fn floyd<S, F>(init: S, f: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: Fn(S) -> S,
+{
+    let mut tortoise = f(init.clone());
+    let mut hare = f(f(init.clone()));
+    while tortoise != hare {
+        tortoise = f(tortoise);
+        hare = f(f(hare));
+    }
+
+    // the hare is now a multiple of the cycle length ahead,
+    // so walking both at the same speed meets at the start of the cycle
+    let mut start = 0;
+    tortoise = init;
+    while tortoise != hare {
+        tortoise = f(tortoise);
+        hare = f(hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = f(tortoise.clone());
+    while tortoise != hare {
+        hare = f(hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// brent's algorithm, constant memory and fewer calls to `f` than floyd
+pub fn brent<S, F>(init: S, f: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: Fn(S) -> S,
+{
+    // teleport the tortoise to the hare at every power of two
+    // until the hare catches up with it
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = init.clone();
+    let mut hare = f(init.clone());
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(hare);
+        length += 1;
+    }
+
+    // start the hare one cycle length ahead, then walk both until they meet
+    let mut start = 0;
+    tortoise = init.clone();
+    hare = init;
+    for _ in 0..length {
+        hare = f(hare);
+    }
+    while tortoise != hare {
+        tortoise = f(tortoise);
+        hare = f(hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// remembers every state it has seen, so it only walks the sequence once
+/// at the cost of memory and requiring `Hash`
+pub fn hashed<S, F>(init: S, f: F) -> Cycle
+where
+    S: Clone + Eq + Hash,
+    F: Fn(S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut state = init;
+    let mut i = 0;
+    loop {
+        if let Some(start) = seen.get(&state) {
+            return Cycle {
+                start: *start,
+                length: i - start,
+            };
+        }
+        seen.insert(state.clone(), i);
+        state = f(state);
+        i += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3 -> ...
+    fn rho(n: u32) -> u32 {
+        if n == 6 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn test_rho() {
+        let expected = Cycle {
+            start: 3,
+            length: 4,
+        };
+        assert_eq!(floyd(0, rho), expected);
+        assert_eq!(brent(0, rho), expected);
+        assert_eq!(hashed(0, rho), expected);
+    }
+
+    #[test]
+    fn test_pure_cycle() {
+        let expected = Cycle {
+            start: 0,
+            length: 7,
+        };
+        let f = |n: u32| (n + 1) % 7;
+        assert_eq!(floyd(0, f), expected);
+        assert_eq!(brent(0, f), expected);
+        assert_eq!(hashed(0, f), expected);
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        let expected = Cycle {
+            start: 0,
+            length: 1,
+        };
+        assert_eq!(floyd(5, |n: u32| n), expected);
+        assert_eq!(brent(5, |n: u32| n), expected);
+        assert_eq!(hashed(5, |n: u32| n), expected);
+    }
+
+    #[test]
+    fn test_algorithms_agree() {
+        for seed in 0..50_u64 {
+            let f = |n: u64| (n * n + seed) % 1009;
+            let cycle = hashed(seed, f);
+            assert_eq!(floyd(seed, f), cycle);
+            assert_eq!(brent(seed, f), cycle);
+        }
+    }
+
+    #[test]
+    fn test_states() {
+        let cycle = brent(0, rho);
+        assert_eq!(cycle.states(0, rho), vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(cycle.index_of(2), 2);
+        assert_eq!(cycle.index_of(7), 3);
+        assert_eq!(cycle.index_of(12), 4);
+    }
+}
@@ -0,0 +1,157 @@
+// puzzle inputs get saved from browsers and editors in all sorts of ways,
+// so everything goes through here before it reaches a parser
+
+use std::fmt::Display;
+
+const BOM: char = '\u{feff}';
+
+/// something about an input that `normalize` would quietly fix.
+/// lines are numbered from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Irregularity {
+    ByteOrderMark,
+    CarriageReturn { line: usize },
+    TrailingWhitespace { line: usize },
+    TrailingBlankLines(usize),
+}
+
+impl Display for Irregularity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Irregularity::ByteOrderMark => {
+                write!(f, "starts with a byte order mark")
+            }
+            Irregularity::CarriageReturn { line } => {
+                write!(f, "line {} ends with \\r\\n", line)
+            }
+            Irregularity::TrailingWhitespace { line } => {
+                write!(f, "line {} has trailing whitespace", line)
+            }
+            Irregularity::TrailingBlankLines(n) => {
+                write!(f, "ends with {} blank line(s)", n)
+            }
+        }
+    }
+}
+
+/// strips any byte order mark, converts crlf to lf, trims whitespace from
+/// the end of each line and drops blank lines at the end of the input,
+/// including the final newline
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix(BOM).unwrap_or(input);
+    let lines: Vec<&str> = input.lines().map(str::trim_end).collect();
+    let end = lines
+        .iter()
+        .rposition(|l| !l.is_empty())
+        .map_or(0, |i| i + 1);
+    lines[..end].join("\n")
+}
+
+/// everything `normalize` would change, apart from the single newline
+/// most files end with
+pub fn check(input: &str) -> Vec<Irregularity> {
+    let mut res = Vec::new();
+    let input = match input.strip_prefix(BOM) {
+        Some(rest) => {
+            res.push(Irregularity::ByteOrderMark);
+            rest
+        }
+        None => input,
+    };
+
+    let body = input.strip_suffix('\n').unwrap_or(input);
+    let lines: Vec<&str> = body.split('\n').collect();
+    let blank_at_end = lines
+        .iter()
+        .rev()
+        .take_while(|l| l.trim().is_empty())
+        .count()
+        .min(lines.len() - 1);
+
+    for (i, line) in lines[..lines.len() - blank_at_end].iter().enumerate() {
+        let line_no = i + 1;
+        if let Some(line) = line.strip_suffix('\r') {
+            res.push(Irregularity::CarriageReturn { line: line_no });
+            if line != line.trim_end() {
+                res.push(Irregularity::TrailingWhitespace { line: line_no });
+            }
+        } else if *line != line.trim_end() {
+            res.push(Irregularity::TrailingWhitespace { line: line_no });
+        }
+    }
+
+    if blank_at_end > 0 {
+        res.push(Irregularity::TrailingBlankLines(blank_at_end));
+    }
+    res
+}
+
+/// like `normalize`, but refuses any input it would have had to change
+pub fn normalize_strict(input: &str) -> Result<String, Vec<Irregularity>> {
+    let irregularities = check(input);
+    if irregularities.is_empty() {
+        Ok(normalize(input))
+    } else {
+        Err(irregularities)
+    }
+}
+
+/// reads a file and normalizes it
+pub fn read(filename: &str) -> String {
+    let input = std::fs::read_to_string(filename)
+        .unwrap_or_else(|e| panic!("couldn't read {}: {}", filename, e));
+    normalize(&input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("1 2\n3 4\n"), "1 2\n3 4");
+        assert_eq!(normalize("\u{feff}1 2\r\n3 4\r\n"), "1 2\n3 4");
+        assert_eq!(normalize("1 2  \n3 4\t\n\n\n"), "1 2\n3 4");
+        assert_eq!(normalize("a\n\nb\n"), "a\n\nb");
+        assert_eq!(normalize("  a\n"), "  a");
+        assert_eq!(normalize("\n\n"), "");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn test_check_regular() {
+        assert_eq!(check("1 2\n3 4\n"), vec![]);
+        assert_eq!(check("1 2\n3 4"), vec![]);
+        assert_eq!(check("a\n\nb\n"), vec![]);
+        assert_eq!(check(""), vec![]);
+    }
+
+    #[test]
+    fn test_check_irregular() {
+        assert_eq!(
+            check("\u{feff}1 2\r\n3 4 \n\n\n"),
+            vec![
+                Irregularity::ByteOrderMark,
+                Irregularity::CarriageReturn { line: 1 },
+                Irregularity::TrailingWhitespace { line: 2 },
+                Irregularity::TrailingBlankLines(2),
+            ]
+        );
+        assert_eq!(
+            check("a \r\n"),
+            vec![
+                Irregularity::CarriageReturn { line: 1 },
+                Irregularity::TrailingWhitespace { line: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strict() {
+        assert_eq!(normalize_strict("1 2\n"), Ok("1 2".to_string()));
+        assert_eq!(
+            normalize_strict("1 2 \n"),
+            Err(vec![Irregularity::TrailingWhitespace { line: 1 }])
+        );
+    }
+}
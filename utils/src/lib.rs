@@ -0,0 +1,8 @@
+pub mod answer;
+pub mod counter;
+pub mod cycle;
+pub mod input;
+pub mod progress;
+pub mod visited;
+
+pub use counter::Counter;
@@ -0,0 +1,73 @@
+// long-running solvers say how far they've got through here, and find out
+// whether they should give up
+
+use std::fmt::Display;
+
+/// returned by a solver that was asked to stop part way through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled {
+    pub done: usize,
+    pub total: usize,
+}
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cancelled after {} of {}", self.done, self.total)
+    }
+}
+
+pub trait Progress {
+    /// called with how many of the solver's `total` items are `done`.
+    /// an error means the solver should stop and pass it on
+    fn update(&mut self, done: usize, total: usize) -> Result<(), Cancelled>;
+}
+
+/// a closure returning whether to carry on
+impl<F: FnMut(usize, usize) -> bool> Progress for F {
+    fn update(&mut self, done: usize, total: usize) -> Result<(), Cancelled> {
+        if self(done, total) {
+            Ok(())
+        } else {
+            Err(Cancelled { done, total })
+        }
+    }
+}
+
+/// for when nobody's watching, never cancels
+pub struct Ignore;
+
+impl Progress for Ignore {
+    fn update(&mut self, _: usize, _: usize) -> Result<(), Cancelled> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_to(
+        n: usize,
+        progress: &mut dyn Progress,
+    ) -> Result<usize, Cancelled> {
+        for i in 1..=n {
+            progress.update(i, n)?;
+        }
+        Ok(n)
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut seen = Vec::new();
+        let mut record = |done, total| {
+            seen.push((done, total));
+            done < 2
+        };
+        assert_eq!(
+            count_to(3, &mut record),
+            Err(Cancelled { done: 2, total: 3 })
+        );
+        assert_eq!(seen, vec![(1, 3), (2, 3)]);
+        assert_eq!(count_to(3, &mut Ignore), Ok(3));
+    }
+}
@@ -0,0 +1,124 @@
+// where something walking a grid has been, for walkers whose state is a
+// cell plus one of a few small states, like the way they're facing. a bit
+// per state per cell, so checking and marking are a shift and a mask
+
+const BITS: usize = u64::BITS as usize;
+
+/// a dense set of (x, y, state) on a `width` x `height` grid.
+/// each state gets its own plane of bits, so the cells visited in any
+/// state can be counted by or-ing the planes together a word at a time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Visited {
+    width: usize,
+    height: usize,
+    states: usize,
+    /// words per plane, so every plane starts on a word boundary
+    plane: usize,
+    bits: Vec<u64>,
+}
+
+impl Visited {
+    pub fn new(width: usize, height: usize, states: usize) -> Visited {
+        let plane = (width * height).div_ceil(BITS);
+        Visited {
+            width,
+            height,
+            states,
+            plane,
+            bits: vec![0; plane * states],
+        }
+    }
+
+    fn index(&self, x: usize, y: usize, state: usize) -> (usize, u64) {
+        assert!(
+            x < self.width && y < self.height && state < self.states,
+            "({}, {}, {}) is outside a {} x {} x {} grid",
+            x,
+            y,
+            state,
+            self.width,
+            self.height,
+            self.states
+        );
+        let bit = y * self.width + x;
+        (state * self.plane + bit / BITS, 1 << (bit % BITS))
+    }
+
+    /// marks the state as visited, returning false if it already was
+    pub fn insert(&mut self, x: usize, y: usize, state: usize) -> bool {
+        let (word, mask) = self.index(x, y, state);
+        let new = self.bits[word] & mask == 0;
+        self.bits[word] |= mask;
+        new
+    }
+
+    pub fn contains(&self, x: usize, y: usize, state: usize) -> bool {
+        let (word, mask) = self.index(x, y, state);
+        self.bits[word] & mask != 0
+    }
+
+    /// how many (x, y, state)s have been visited
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|w| *w == 0)
+    }
+
+    /// how many cells have been visited, in any state
+    pub fn cells(&self) -> usize {
+        (0..self.plane)
+            .map(|i| {
+                let word = (0..self.states)
+                    .fold(0, |acc, s| acc | self.bits[s * self.plane + i]);
+                word.count_ones() as usize
+            })
+            .sum()
+    }
+
+    /// forgets everything, keeping the memory for another walk
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert() {
+        let mut visited = Visited::new(3, 2, 4);
+        assert!(visited.is_empty());
+        assert!(visited.insert(2, 1, 3));
+        assert!(!visited.insert(2, 1, 3));
+        assert!(visited.contains(2, 1, 3));
+        assert!(!visited.contains(2, 1, 2));
+        assert!(!visited.contains(1, 1, 3));
+        assert_eq!(visited.len(), 1);
+
+        visited.clear();
+        assert!(visited.is_empty());
+        assert!(!visited.contains(2, 1, 3));
+    }
+
+    #[test]
+    fn test_cells() {
+        // wide enough that the planes take more than one word each
+        let mut visited = Visited::new(100, 3, 4);
+        visited.insert(0, 0, 0);
+        visited.insert(0, 0, 1);
+        visited.insert(99, 2, 3);
+        visited.insert(70, 0, 2);
+        visited.insert(70, 0, 3);
+        assert_eq!(visited.len(), 5);
+        assert_eq!(visited.cells(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside a 3 x 2 x 4 grid")]
+    fn test_out_of_bounds() {
+        Visited::new(3, 2, 4).insert(3, 0, 0);
+    }
+}